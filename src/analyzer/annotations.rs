@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::error::Result;
+
+/// Counts the annotations left on the pages, such as comments and highlights,
+/// and the form fields still fillable. Flattened documents have none.
+pub struct AnnotationAnalyzer;
+
+/// Name shown for an annotation subtype; `Text` annotations are sticky notes.
+fn type_name(annotation_type: PdfPageAnnotationType) -> String {
+    match annotation_type {
+        PdfPageAnnotationType::Text => "Note".to_string(),
+        PdfPageAnnotationType::FreeText => "Text Box".to_string(),
+        PdfPageAnnotationType::Widget | PdfPageAnnotationType::XfaWidget => {
+            "Form Field".to_string()
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+impl Analyzer for AnnotationAnalyzer {
+    fn id(&self) -> &'static str {
+        "annotations"
+    }
+
+    fn name(&self) -> &'static str {
+        "Annotations"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut total = 0;
+        let mut by_type: Vec<(String, usize)> = Vec::new();
+        // Counted once per fully qualified name, like the form analysis
+        let mut field_names = HashSet::new();
+        let mut unnamed_fields = 0;
+
+        for (_, page) in selected_pages(document, pages) {
+            for annotation in page.annotations().iter() {
+                let annotation_type = annotation.annotation_type();
+                // Popups only show the contents of the annotation they belong to
+                if annotation_type == PdfPageAnnotationType::Popup {
+                    continue;
+                }
+
+                total += 1;
+                let name = type_name(annotation_type);
+                match by_type.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, count)) => *count += 1,
+                    None => by_type.push((name, 1)),
+                }
+
+                if let Some(field) = annotation.as_form_field() {
+                    match field.name() {
+                        Some(name) => {
+                            field_names.insert(name);
+                        }
+                        None => unnamed_fields += 1,
+                    }
+                }
+            }
+        }
+
+        // Most frequent first
+        by_type.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(AnalysisResult::Annotations {
+            total,
+            by_type,
+            form_fields: field_names.len() + unnamed_fields,
+        })
+    }
+}
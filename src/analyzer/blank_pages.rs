@@ -0,0 +1,101 @@
+use std::ops::Range;
+use std::path::Path;
+
+use image::GenericImageView;
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::{AppError, Result};
+
+pub struct BlankPageAnalyzer {
+    blank_luminance_threshold: f64,
+}
+
+impl Default for BlankPageAnalyzer {
+    fn default() -> Self {
+        Self {
+            blank_luminance_threshold: 245.0,
+        }
+    }
+}
+
+impl BlankPageAnalyzer {
+    fn is_page_blank(&self, page: &PdfPage) -> Result<bool> {
+        let render_config = PdfRenderConfig::new()
+            .set_target_width(100)
+            .set_maximum_height(150);
+
+        let bitmap =
+            page.render_with_config(&render_config)
+                .map_err(|e| AppError::RenderError {
+                    page: 0,
+                    reason: e.to_string(),
+                })?;
+
+        let image = bitmap.as_image();
+
+        // At this resolution checking every pixel is cheap
+        for (_, _, pixel) in image.pixels() {
+            let [r, g, b, _] = pixel.0;
+            let luminance = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+            if luminance < self.blank_luminance_threshold {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Analyzer for BlankPageAnalyzer {
+    fn id(&self) -> &'static str {
+        "blank_pages"
+    }
+
+    fn name(&self) -> &'static str {
+        "Blank Pages"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut blank_page_indices = Vec::new();
+
+        for (index, page) in selected_pages(document, pages) {
+            // Count the page as printed content if it can't be rendered
+            if let Ok(true) = self.is_page_blank(&page) {
+                blank_page_indices.push(index + 1);
+            }
+        }
+
+        Ok(AnalysisResult::BlankPages {
+            blank_count: blank_page_indices.len(),
+            blank_page_indices,
+        })
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "blank_luminance_threshold",
+            label: "Blank luminance threshold",
+            default: ConfigValue::Float(245.0),
+            description: "Pages whose pixels are all at least this bright (0-255) count as blank",
+            min: Some(ConfigValue::Float(0.0)),
+            max: Some(ConfigValue::Float(255.0)),
+            step: Some(1.0),
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Float(v)) =
+            config.get_analyzer_value(self.id(), "blank_luminance_threshold")
+        {
+            self.blank_luminance_threshold = *v;
+        }
+    }
+}
@@ -1,18 +1,39 @@
+use std::ops::Range;
 use std::path::Path;
 
 use image::GenericImageView;
 use pdfium_render::prelude::*;
 
-use super::{AnalysisResult, Analyzer};
+use super::{selected_pages, AnalysisResult, Analyzer, CancellationToken};
+use crate::config::{Config, ConfigParam, ConfigValue};
 use crate::error::{AppError, Result};
 
-pub struct ColorAnalysisAnalyzer;
+pub struct ColorAnalysisAnalyzer {
+    render_width: i64,
+    sample_density: f64,
+    /// Largest difference between a pixel's RGB channels still counted as gray
+    color_threshold: u8,
+    per_page: bool,
+}
+
+impl Default for ColorAnalysisAnalyzer {
+    fn default() -> Self {
+        Self {
+            render_width: 200,
+            sample_density: 0.1,
+            color_threshold: 10,
+            per_page: false,
+        }
+    }
+}
 
 impl ColorAnalysisAnalyzer {
-    fn is_page_color(page: &PdfPage) -> Result<bool> {
+    /// Fraction of the sampled pixels that are in color, 0.0 for a gray page.
+    fn page_color_coverage(&self, page: &PdfPage) -> Result<f32> {
+        let render_width = self.render_width.max(1) as i32;
         let render_config = PdfRenderConfig::new()
-            .set_target_width(200)
-            .set_maximum_height(300);
+            .set_target_width(render_width)
+            .set_maximum_height(render_width * 3 / 2);
 
         let bitmap = page.render_with_config(&render_config).map_err(|e| {
             AppError::RenderError {
@@ -23,27 +44,33 @@ impl ColorAnalysisAnalyzer {
 
         let image = bitmap.as_image();
 
-        // Sample pixels across the image
+        // Sample pixels across the image; a density of 1.0 visits every pixel
         let width = image.width();
         let height = image.height();
-        let step_x = (width / 20).max(1);
-        let step_y = (height / 20).max(1);
+        let density = self.sample_density.clamp(0.001, 1.0);
+        let step = (1.0 / density).round().max(1.0) as usize;
 
-        for y in (0..height).step_by(step_y as usize) {
-            for x in (0..width).step_by(step_x as usize) {
+        let mut sampled = 0usize;
+        let mut colored = 0usize;
+        for y in (0..height).step_by(step) {
+            for x in (0..width).step_by(step) {
                 let pixel = image.get_pixel(x, y);
                 let [r, g, b, _] = pixel.0;
 
                 // Check if pixel is colored (not grayscale)
                 // Allow small tolerance for compression artifacts
                 let max_diff = r.abs_diff(g).max(r.abs_diff(b)).max(g.abs_diff(b));
-                if max_diff > 10 {
-                    return Ok(true);
+                if max_diff > self.color_threshold {
+                    colored += 1;
                 }
+                sampled += 1;
             }
         }
 
-        Ok(false)
+        if sampled == 0 {
+            return Ok(0.0);
+        }
+        Ok(colored as f32 / sampled as f32)
     }
 }
 
@@ -56,21 +83,132 @@ impl Analyzer for ColorAnalysisAnalyzer {
         "Color Analysis"
     }
 
-    fn analyze(&self, document: &PdfDocument, _path: &Path) -> Result<AnalysisResult> {
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        self.analyze_with_progress(document, path, pages, &|_, _| {}, &CancellationToken::new())
+    }
+
+    fn analyze_with_progress(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+        progress: &dyn Fn(usize, usize),
+        cancel: &CancellationToken,
+    ) -> Result<AnalysisResult> {
         let mut bw_pages = 0;
         let mut color_pages = 0;
+        let mut color_page_numbers = Vec::new();
+        let mut color_page_coverage = Vec::new();
+        let mut coverage_sum = 0.0f32;
+        let mut max_color_coverage = 0.0f32;
+        let mut page_results = Vec::new();
+        let page_total = pages.len();
 
-        for page in document.pages().iter() {
-            match Self::is_page_color(&page) {
-                Ok(true) => color_pages += 1,
-                Ok(false) => bw_pages += 1,
-                Err(_) => bw_pages += 1, // Default to B&W on error
+        for (done, (index, page)) in selected_pages(document, pages).enumerate() {
+            if cancel.is_cancelled() {
+                return Err(AppError::Cancelled);
             }
+            progress(done + 1, page_total);
+            // Default to B&W on error
+            let coverage = self.page_color_coverage(&page).unwrap_or(0.0);
+            let is_color = coverage > 0.0;
+            coverage_sum += coverage;
+            max_color_coverage = max_color_coverage.max(coverage);
+            if is_color {
+                color_pages += 1;
+                color_page_numbers.push(index + 1);
+                color_page_coverage.push(coverage);
+            } else {
+                bw_pages += 1;
+            }
+            page_results.push((index + 1, is_color));
         }
 
+        let avg_color_coverage = if page_total > 0 {
+            coverage_sum / page_total as f32
+        } else {
+            0.0
+        };
+
+        if self.per_page {
+            return Ok(AnalysisResult::ColorAnalysisDetailed {
+                bw_pages,
+                color_pages,
+                color_page_numbers,
+                color_page_coverage,
+                avg_color_coverage,
+                max_color_coverage,
+                page_results,
+            });
+        }
         Ok(AnalysisResult::ColorAnalysis {
             bw_pages,
             color_pages,
+            color_page_numbers,
+            color_page_coverage,
+            avg_color_coverage,
+            max_color_coverage,
         })
     }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![
+            ConfigParam {
+                key: "render_width",
+                label: "Render width (px)",
+                default: ConfigValue::Int(200),
+                description: "Width pages are rendered at; higher values find smaller color marks",
+                min: Some(ConfigValue::Int(1)),
+                step: Some(10.0),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "sample_density",
+                label: "Sampling density",
+                default: ConfigValue::Float(0.1),
+                description: "Fraction of pixels examined along each axis; 1.0 checks every pixel",
+                min: Some(ConfigValue::Float(0.001)),
+                max: Some(ConfigValue::Float(1.0)),
+                step: Some(0.001),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "color_threshold",
+                label: "Color threshold",
+                default: ConfigValue::Int(10),
+                description: "Pixels whose RGB channels differ by more than this count as color",
+                min: Some(ConfigValue::Int(0)),
+                max: Some(ConfigValue::Int(255)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "per_page",
+                label: "Per-page results",
+                default: ConfigValue::Bool(false),
+                description: "Keep whether each single page is in color, not only the totals",
+                ..ConfigParam::default()
+            },
+        ]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Int(v)) = config.get_analyzer_value(self.id(), "render_width") {
+            self.render_width = *v;
+        }
+        if let Some(ConfigValue::Float(v)) = config.get_analyzer_value(self.id(), "sample_density")
+        {
+            self.sample_density = *v;
+        }
+        if let Some(ConfigValue::Int(v)) = config.get_analyzer_value(self.id(), "color_threshold") {
+            self.color_threshold = (*v).clamp(0, 255) as u8;
+        }
+        if let Some(ConfigValue::Bool(v)) = config.get_analyzer_value(self.id(), "per_page") {
+            self.per_page = *v;
+        }
+    }
 }
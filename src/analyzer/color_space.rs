@@ -0,0 +1,121 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::raw::{contains_name, find, read_name, skip_whitespace};
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::error::Result;
+
+/// Colorant names that are part of process printing rather than spot colors.
+const PROCESS_COLORANTS: &[&str] = &["Cyan", "Magenta", "Yellow", "Black", "All", "None"];
+
+pub struct ColorSpaceAnalyzer;
+
+impl ColorSpaceAnalyzer {
+    /// Spot colorants named by `/Separation` and `/DeviceN` color spaces.
+    fn spot_colors(bytes: &[u8]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut add = |name: String| {
+            if !PROCESS_COLORANTS.contains(&name.as_str()) && !names.contains(&name) {
+                names.push(name);
+            }
+        };
+
+        // [/Separation /Name alternate tint]
+        let mut from = 0;
+        while let Some(pos) = find(bytes, b"/Separation", from) {
+            from = pos + 1;
+            let start = skip_whitespace(bytes, pos + b"/Separation".len());
+            if let Some((name, _)) = read_name(bytes, start) {
+                add(name);
+            }
+        }
+
+        // [/DeviceN [/Name1 /Name2 ...] alternate tint]
+        let mut from = 0;
+        while let Some(pos) = find(bytes, b"/DeviceN", from) {
+            from = pos + 1;
+            let mut i = skip_whitespace(bytes, pos + b"/DeviceN".len());
+            if bytes.get(i) != Some(&b'[') {
+                continue;
+            }
+            i = skip_whitespace(bytes, i + 1);
+            while let Some((name, end)) = read_name(bytes, i) {
+                add(name);
+                i = skip_whitespace(bytes, end);
+            }
+        }
+
+        names
+    }
+}
+
+impl Analyzer for ColorSpaceAnalyzer {
+    fn id(&self) -> &'static str {
+        "color_space"
+    }
+
+    fn name(&self) -> &'static str {
+        "Color Space"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        // Vector and text colors are set through /ColorSpace resources, which
+        // pdfium doesn't expose, so those are read from the raw file.
+        let bytes = fs::read(path)?;
+        let mut has_cmyk = contains_name(&bytes, b"/DeviceCMYK");
+        let mut has_rgb = contains_name(&bytes, b"/DeviceRGB") || contains_name(&bytes, b"/CalRGB");
+
+        // Image color spaces are also available when stored compressed, and per page
+        let mut rgb_page_numbers = Vec::new();
+        let mut cmyk_pages = 0;
+        let mut gray_pages = 0;
+        for (index, page) in selected_pages(document, pages) {
+            let mut page_rgb = false;
+            let mut page_cmyk = false;
+            let mut page_gray = false;
+            for object in page.objects().iter() {
+                let Some(image) = object.as_image_object() else {
+                    continue;
+                };
+                match image.color_space() {
+                    Ok(PdfColorSpace::DeviceCMYK) => page_cmyk = true,
+                    Ok(PdfColorSpace::DeviceRGB | PdfColorSpace::CalibratedCIERGB) => {
+                        page_rgb = true
+                    }
+                    Ok(PdfColorSpace::DeviceGray | PdfColorSpace::CalibratedCIEGray) => {
+                        page_gray = true
+                    }
+                    _ => {}
+                }
+            }
+            has_rgb |= page_rgb;
+            has_cmyk |= page_cmyk;
+            if page_rgb {
+                rgb_page_numbers.push(index + 1);
+            }
+            cmyk_pages += page_cmyk as usize;
+            gray_pages += page_gray as usize;
+        }
+
+        let spot_color_names = Self::spot_colors(&bytes);
+
+        Ok(AnalysisResult::ColorSpaceAnalysis {
+            has_cmyk,
+            has_rgb,
+            has_spot_colors: !spot_color_names.is_empty(),
+            spot_color_names,
+            rgb_pages: rgb_page_numbers.len(),
+            rgb_page_numbers,
+            cmyk_pages,
+            gray_pages,
+        })
+    }
+}
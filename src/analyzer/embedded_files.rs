@@ -0,0 +1,40 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+
+pub struct EmbeddedFilesAnalyzer;
+
+impl Analyzer for EmbeddedFilesAnalyzer {
+    fn id(&self) -> &'static str {
+        "embedded_files"
+    }
+
+    fn name(&self) -> &'static str {
+        "Embedded Files"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut total_size_bytes = 0u64;
+        let mut filenames = Vec::new();
+
+        for attachment in document.attachments().iter() {
+            total_size_bytes += attachment.len() as u64;
+            filenames.push(attachment.name());
+        }
+
+        Ok(AnalysisResult::EmbeddedFilesAnalysis {
+            count: filenames.len(),
+            total_size_bytes,
+            filenames,
+        })
+    }
+}
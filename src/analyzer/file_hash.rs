@@ -0,0 +1,32 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+use crate::pdf::hash_file;
+
+/// SHA-256 of the file's bytes, to find identical files across the batch.
+pub struct FileHashAnalyzer;
+
+impl Analyzer for FileHashAnalyzer {
+    fn id(&self) -> &'static str {
+        "file_hash"
+    }
+
+    fn name(&self) -> &'static str {
+        "File Hash"
+    }
+
+    fn analyze(
+        &self,
+        _document: &PdfDocument,
+        path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        Ok(AnalysisResult::FileHash {
+            sha256: hash_file(path)?,
+        })
+    }
+}
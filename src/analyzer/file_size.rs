@@ -0,0 +1,37 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+
+/// Size of the file on disk, from its metadata without rendering anything.
+pub struct FileSizeAnalyzer;
+
+impl Analyzer for FileSizeAnalyzer {
+    fn id(&self) -> &'static str {
+        "file_size"
+    }
+
+    fn name(&self) -> &'static str {
+        "File Size"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let bytes = fs::metadata(path)?.len();
+        // The size covers the whole file, so it is divided by all of its pages
+        let page_count = document.pages().len() as u64;
+        let bytes_per_page = bytes.checked_div(page_count).unwrap_or(0);
+        Ok(AnalysisResult::FileSize {
+            bytes,
+            bytes_per_page,
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::error::{AppError, Result};
+
+pub struct FormAnalyzer;
+
+impl Analyzer for FormAnalyzer {
+    fn id(&self) -> &'static str {
+        "form_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Form Analysis"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let form_type = document
+            .form()
+            .map(|form| form.form_type())
+            .unwrap_or(PdfFormType::None);
+
+        let has_acroform = form_type != PdfFormType::None;
+        // pdfium only reports an XFA form type when the AcroForm dict carries /XFA
+        let has_xfa = matches!(form_type, PdfFormType::XfaFull | PdfFormType::XfaForeground);
+
+        // Radio groups and fields shown on several pages have one widget per
+        // occurrence, so fields are counted once per fully qualified name.
+        let mut field_names = HashSet::new();
+        let mut unnamed_fields = 0;
+        let mut required_fields = 0;
+
+        for (_, page) in selected_pages(document, pages) {
+            for annotation in page.annotations().iter() {
+                let Some(field) = annotation.as_form_field() else {
+                    continue;
+                };
+
+                let is_new = match field.name() {
+                    Some(name) => field_names.insert(name),
+                    None => {
+                        unnamed_fields += 1;
+                        true
+                    }
+                };
+
+                if is_new && field.is_required() {
+                    required_fields += 1;
+                }
+            }
+        }
+
+        if required_fields > 0 && !has_acroform {
+            return Err(AppError::AnalyzerError {
+                analyzer: self.name().to_string(),
+                file: path.display().to_string(),
+                reason: format!(
+                    "{} required form fields found without an AcroForm dictionary",
+                    required_fields
+                ),
+            });
+        }
+
+        Ok(AnalysisResult::FormAnalysis {
+            has_acroform,
+            field_count: field_names.len() + unnamed_fields,
+            has_xfa,
+            required_fields,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::error::Result;
+
+/// Maximum number of distinct URLs kept per document.
+pub const MAX_UNIQUE_URLS: usize = 100;
+
+pub struct HyperlinkAnalyzer;
+
+impl Analyzer for HyperlinkAnalyzer {
+    fn id(&self) -> &'static str {
+        "hyperlink_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Hyperlink Analysis"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut total_links = 0;
+        let mut unique_urls: Vec<String> = Vec::new();
+        let mut broken_format_count = 0;
+
+        for (_, page) in selected_pages(document, pages) {
+            for link in page.links().iter() {
+                // Links to destinations inside the document are not hyperlinks
+                let Some(uri) = link
+                    .action()
+                    .and_then(|action| action.as_uri_action().and_then(|a| a.uri().ok()))
+                else {
+                    continue;
+                };
+
+                total_links += 1;
+
+                if url::Url::parse(uri.trim()).is_err() {
+                    broken_format_count += 1;
+                }
+
+                if unique_urls.len() < MAX_UNIQUE_URLS && !unique_urls.contains(&uri) {
+                    unique_urls.push(uri);
+                }
+            }
+        }
+
+        Ok(AnalysisResult::HyperlinkAnalysis {
+            total_links,
+            unique_urls,
+            broken_format_count,
+        })
+    }
+}
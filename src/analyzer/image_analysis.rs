@@ -0,0 +1,157 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+pub struct ImageAnalyzer {
+    min_dpi_threshold: f64,
+    warn_on_low_dpi: bool,
+}
+
+impl Default for ImageAnalyzer {
+    fn default() -> Self {
+        Self {
+            min_dpi_threshold: 150.0,
+            warn_on_low_dpi: true,
+        }
+    }
+}
+
+impl ImageAnalyzer {
+    /// Effective DPI of an image object: its natural pixel size divided by the
+    /// size it is drawn at on the page (taken from the transform matrix).
+    /// Returns the lower of the horizontal and vertical resolution.
+    fn effective_dpi(image: &PdfPageImageObject) -> Option<f32> {
+        let width_px = image.width().ok()? as f32;
+        let height_px = image.height().ok()? as f32;
+        let matrix = image.matrix().ok()?;
+
+        // The image is mapped from the unit square, so the length of the
+        // matrix column vectors is the drawn size in points.
+        let width_pt = (matrix.a() * matrix.a() + matrix.b() * matrix.b()).sqrt();
+        let height_pt = (matrix.c() * matrix.c() + matrix.d() * matrix.d()).sqrt();
+
+        if width_pt <= 0.0 || height_pt <= 0.0 {
+            return None;
+        }
+
+        let dpi_x = width_px / (width_pt / 72.0);
+        let dpi_y = height_px / (height_pt / 72.0);
+        Some(dpi_x.min(dpi_y))
+    }
+}
+
+impl Analyzer for ImageAnalyzer {
+    fn id(&self) -> &'static str {
+        "image_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Image Analysis"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut total_images = 0;
+        let mut below_threshold = 0;
+        let mut min_dpi: Option<f32> = None;
+        let mut compression_types: Vec<String> = Vec::new();
+        let mut low_dpi_pages: Vec<(usize, f32)> = Vec::new();
+
+        for (index, page) in selected_pages(document, pages) {
+            let mut page_low_dpi: Option<f32> = None;
+
+            for object in page.objects().iter() {
+                let Some(image) = object.as_image_object() else {
+                    continue;
+                };
+
+                total_images += 1;
+
+                if let Some(dpi) = Self::effective_dpi(image) {
+                    if (dpi as f64) < self.min_dpi_threshold {
+                        below_threshold += 1;
+                        page_low_dpi = Some(page_low_dpi.map_or(dpi, |m| m.min(dpi)));
+                    }
+                    min_dpi = Some(min_dpi.map_or(dpi, |m| m.min(dpi)));
+                }
+
+                for filter in image.filters().iter() {
+                    let name = filter.name().to_string();
+                    if !compression_types.contains(&name) {
+                        compression_types.push(name);
+                    }
+                }
+            }
+
+            if let Some(dpi) = page_low_dpi {
+                low_dpi_pages.push((index + 1, dpi));
+            }
+        }
+
+        Ok(AnalysisResult::ImageAnalysis {
+            total_images,
+            below_threshold,
+            min_dpi,
+            compression_types,
+            low_dpi_pages,
+        })
+    }
+
+    fn result_errors(&self, result: &AnalysisResult) -> Vec<String> {
+        match result {
+            AnalysisResult::ImageAnalysis { low_dpi_pages, .. }
+                if self.warn_on_low_dpi && !low_dpi_pages.is_empty() =>
+            {
+                let pages: Vec<String> = low_dpi_pages
+                    .iter()
+                    .map(|(page, dpi)| format!("p. {} ({:.0} DPI)", page, dpi))
+                    .collect();
+                vec![format!("Low-resolution images: {}", pages.join(", "))]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![
+            ConfigParam {
+                key: "min_dpi_threshold",
+                label: "Minimum image DPI",
+                default: ConfigValue::Float(150.0),
+                description: "Images below this effective resolution are reported as low-DPI",
+                min: Some(ConfigValue::Float(0.0)),
+                max: Some(ConfigValue::Float(1200.0)),
+                step: Some(1.0),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "warn_on_low_dpi",
+                label: "Warn on low-DPI images",
+                default: ConfigValue::Bool(true),
+                description: "List the pages with low-DPI images in the analysis errors",
+                ..ConfigParam::default()
+            },
+        ]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Float(v)) =
+            config.get_analyzer_value(self.id(), "min_dpi_threshold")
+        {
+            self.min_dpi_threshold = *v;
+        }
+        if let Some(ConfigValue::Bool(v)) = config.get_analyzer_value(self.id(), "warn_on_low_dpi")
+        {
+            self.warn_on_low_dpi = *v;
+        }
+    }
+}
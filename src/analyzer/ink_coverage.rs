@@ -0,0 +1,160 @@
+use std::ops::Range;
+use std::path::Path;
+
+use image::GenericImageView;
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer, CancellationToken};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::{AppError, Result};
+
+/// Channel value above which a pixel is treated as unprinted paper
+const WHITE_LEVEL: u8 = 250;
+
+/// Ink coverage of a single rendered page.
+struct PageCoverage {
+    /// Fraction of non-white pixels
+    coverage: f64,
+    /// Average C, M, Y, K ink amounts (0.0-1.0) estimated from RGB
+    cmyk: [f64; 4],
+}
+
+pub struct InkCoverageAnalyzer {
+    render_width: i64,
+}
+
+impl Default for InkCoverageAnalyzer {
+    fn default() -> Self {
+        Self { render_width: 150 }
+    }
+}
+
+impl InkCoverageAnalyzer {
+    fn page_coverage(&self, page: &PdfPage) -> Result<PageCoverage> {
+        let render_width = self.render_width.max(1) as i32;
+        let render_config = PdfRenderConfig::new()
+            .set_target_width(render_width)
+            .set_maximum_height(render_width * 3 / 2);
+
+        let bitmap =
+            page.render_with_config(&render_config)
+                .map_err(|e| AppError::RenderError {
+                    page: 0,
+                    reason: e.to_string(),
+                })?;
+
+        let image = bitmap.as_image();
+
+        let mut inked = 0usize;
+        let mut total = 0usize;
+        let mut cmyk = [0.0f64; 4];
+
+        for (_, _, pixel) in image.pixels() {
+            let [r, g, b, _] = pixel.0;
+            total += 1;
+
+            if r.min(g).min(b) < WHITE_LEVEL {
+                inked += 1;
+            }
+
+            // Naive RGB to CMYK separation with full black generation
+            let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+            let k = 1.0 - r.max(g).max(b);
+            if k < 1.0 {
+                cmyk[0] += (1.0 - r - k) / (1.0 - k);
+                cmyk[1] += (1.0 - g - k) / (1.0 - k);
+                cmyk[2] += (1.0 - b - k) / (1.0 - k);
+            }
+            cmyk[3] += k;
+        }
+
+        if total == 0 {
+            return Ok(PageCoverage {
+                coverage: 0.0,
+                cmyk,
+            });
+        }
+
+        Ok(PageCoverage {
+            coverage: inked as f64 / total as f64,
+            cmyk: cmyk.map(|channel| channel / total as f64),
+        })
+    }
+}
+
+impl Analyzer for InkCoverageAnalyzer {
+    fn id(&self) -> &'static str {
+        "ink_coverage"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ink Coverage"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        self.analyze_with_progress(document, path, pages, &|_, _| {}, &CancellationToken::new())
+    }
+
+    fn analyze_with_progress(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+        progress: &dyn Fn(usize, usize),
+        cancel: &CancellationToken,
+    ) -> Result<AnalysisResult> {
+        let mut per_page = Vec::new();
+        let mut cmyk_sum = [0.0f64; 4];
+        let page_total = pages.len();
+        let first_page = pages.start + 1;
+
+        for (done, (_, page)) in selected_pages(document, pages).enumerate() {
+            if cancel.is_cancelled() {
+                return Err(AppError::Cancelled);
+            }
+            progress(done + 1, page_total);
+            // Unrenderable pages count as empty rather than failing the document
+            let coverage = self.page_coverage(&page).unwrap_or(PageCoverage {
+                coverage: 0.0,
+                cmyk: [0.0; 4],
+            });
+
+            per_page.push(coverage.coverage);
+            for (sum, channel) in cmyk_sum.iter_mut().zip(coverage.cmyk) {
+                *sum += channel;
+            }
+        }
+
+        let pages = per_page.len().max(1) as f64;
+
+        Ok(AnalysisResult::InkCoverage {
+            avg_coverage: per_page.iter().sum::<f64>() / pages,
+            avg_cmyk: cmyk_sum.map(|sum| sum / pages),
+            per_page,
+            first_page,
+        })
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "render_width",
+            label: "Render width (px)",
+            default: ConfigValue::Int(150),
+            description: "Width pages are rendered at for measuring coverage; higher is more accurate but slower",
+            min: Some(ConfigValue::Int(1)),
+            step: Some(10.0),
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Int(v)) = config.get_analyzer_value(self.id(), "render_width") {
+            self.render_width = *v;
+        }
+    }
+}
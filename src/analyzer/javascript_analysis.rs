@@ -0,0 +1,113 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::raw::has_object_streams;
+use super::{AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+#[derive(Default)]
+pub struct JavaScriptAnalyzer {
+    error_on_javascript: bool,
+}
+
+impl JavaScriptAnalyzer {
+    /// Count `/S /JavaScript` action dictionaries in the raw file.
+    /// pdfium doesn't expose `/OpenAction` or the `/AA` dictionaries of the
+    /// catalog and pages, so these are found by scanning the file itself.
+    /// Actions inside compressed object streams are not visible this way.
+    fn count_raw_actions(bytes: &[u8]) -> usize {
+        const NAME: &[u8] = b"/JavaScript";
+
+        bytes
+            .windows(NAME.len())
+            .enumerate()
+            .filter(|(_, window)| *window == NAME)
+            .filter(|(start, _)| {
+                let before = bytes[..*start].trim_ascii_end();
+                before.ends_with(b"/S")
+            })
+            .count()
+    }
+}
+
+impl Analyzer for JavaScriptAnalyzer {
+    fn id(&self) -> &'static str {
+        "javascript_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "JavaScript Detection"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        // Document level scripts from the /Names tree
+        let bindings = document.bindings();
+        let handle = bindings.get_handle_from_document(document);
+        let named_scripts = bindings.FPDFDoc_GetJavaScriptActionCount(handle).max(0) as usize;
+
+        // The named scripts are action dictionaries too, so they are usually
+        // also part of the raw count unless they sit in an object stream.
+        let bytes = fs::read(path)?;
+        let raw_actions = Self::count_raw_actions(&bytes);
+        let script_count = named_scripts.max(raw_actions);
+
+        Ok(AnalysisResult::JavaScriptAnalysis {
+            has_javascript: script_count > 0,
+            script_count,
+            unscanned_object_streams: has_object_streams(&bytes),
+        })
+    }
+
+    fn result_errors(&self, result: &AnalysisResult) -> Vec<String> {
+        match result {
+            AnalysisResult::JavaScriptAnalysis {
+                has_javascript: true,
+                script_count,
+                ..
+            } if self.error_on_javascript => {
+                vec![format!(
+                    "Document contains {} JavaScript actions",
+                    script_count
+                )]
+            }
+            AnalysisResult::JavaScriptAnalysis {
+                has_javascript: false,
+                unscanned_object_streams: true,
+                ..
+            } if self.error_on_javascript => {
+                vec![
+                    "JavaScript actions in compressed object streams couldn't be checked"
+                        .to_string(),
+                ]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "error_on_javascript",
+            label: "Treat JavaScript as an error",
+            default: ConfigValue::Bool(false),
+            description: "Report PDFs containing JavaScript as failed in the analysis errors",
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Bool(v)) =
+            config.get_analyzer_value(self.id(), "error_on_javascript")
+        {
+            self.error_on_javascript = *v;
+        }
+    }
+}
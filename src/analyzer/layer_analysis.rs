@@ -0,0 +1,122 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::raw::{
+    find, has_name_entry, has_object_streams, objects, read_ref_array, read_string, skip_whitespace,
+};
+use super::{AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+/// pdfium has no API for optional content, so layers are read from the raw
+/// file: every `/Type /OCG` object is a layer, and the default configuration
+/// in `/OCProperties /D` decides which of them start hidden.
+/// Objects inside compressed object streams are not visible this way.
+#[derive(Default)]
+pub struct LayerAnalyzer {
+    required_layer_names: Vec<String>,
+}
+
+impl Analyzer for LayerAnalyzer {
+    fn id(&self) -> &'static str {
+        "layer_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Layer Analysis"
+    }
+
+    fn analyze(
+        &self,
+        _document: &PdfDocument,
+        path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let bytes = fs::read(path)?;
+
+        let mut layers: Vec<(u32, String)> = Vec::new();
+        for (number, body) in objects(&bytes) {
+            if !has_name_entry(body, b"/Type", b"/OCG") {
+                continue;
+            }
+            let name = find(body, b"/Name", 0)
+                .and_then(|pos| read_string(body, skip_whitespace(body, pos + 5)))
+                .unwrap_or_else(|| format!("Layer {}", number));
+            layers.push((number, name));
+        }
+
+        // Layers are on unless listed in /OFF, or off unless listed in /ON
+        // when the default configuration has /BaseState /OFF.
+        let hidden_count = if has_name_entry(&bytes, b"/BaseState", b"/OFF") {
+            let on = read_ref_array(&bytes, b"/ON").unwrap_or_default();
+            layers.iter().filter(|(n, _)| !on.contains(n)).count()
+        } else {
+            let off = read_ref_array(&bytes, b"/OFF").unwrap_or_default();
+            layers.iter().filter(|(n, _)| off.contains(n)).count()
+        };
+
+        Ok(AnalysisResult::LayerAnalysis {
+            has_layers: !layers.is_empty(),
+            visible_count: layers.len() - hidden_count,
+            hidden_count,
+            layer_names: layers.into_iter().map(|(_, name)| name).collect(),
+            unscanned_object_streams: has_object_streams(&bytes),
+        })
+    }
+
+    fn result_errors(&self, result: &AnalysisResult) -> Vec<String> {
+        let AnalysisResult::LayerAnalysis {
+            layer_names,
+            unscanned_object_streams,
+            ..
+        } = result
+        else {
+            return vec![];
+        };
+
+        let missing: Vec<&str> = self
+            .required_layer_names
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .filter(|name| {
+                !layer_names
+                    .iter()
+                    .any(|l| l.trim().eq_ignore_ascii_case(name))
+            })
+            .collect();
+
+        if missing.is_empty() {
+            vec![]
+        } else if *unscanned_object_streams {
+            vec![format!(
+                "Required layers not found: {} (layers in compressed object streams aren't read)",
+                missing.join(", ")
+            )]
+        } else {
+            vec![format!("Missing required layers: {}", missing.join(", "))]
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "required_layer_names",
+            label: "Required layers",
+            default: ConfigValue::List(vec![]),
+            description:
+                "Comma separated layer names every PDF must contain, e.g. Die Cut, Varnish",
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::List(v)) =
+            config.get_analyzer_value(self.id(), "required_layer_names")
+        {
+            self.required_layer_names = v.clone();
+        }
+    }
+}
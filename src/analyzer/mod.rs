@@ -1,31 +1,315 @@
+use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use pdfium_render::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::config::{Config, ConfigParam};
 use crate::error::Result;
 
 pub mod page_count;
+pub mod annotations;
+pub mod blank_pages;
 pub mod color_analysis;
+pub mod color_space;
+pub mod embedded_files;
+pub mod file_hash;
+pub mod file_size;
+pub mod form_analysis;
+pub mod hyperlink_analysis;
+pub mod image_analysis;
+pub mod ink_coverage;
+pub mod javascript_analysis;
+pub mod layer_analysis;
+pub mod orientation;
+pub mod outline;
+pub mod page_rotation;
+pub mod page_size;
+pub mod pdf_version;
+mod raw;
+pub mod security_analysis;
+pub mod signature_analysis;
+pub mod text_content;
+pub mod transparency;
 
-#[derive(Debug, Clone)]
+/// Serialized with a `type` field naming the variant, e.g. `"PageCount"`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum AnalysisResult {
     PageCount { total: usize },
-    ColorAnalysis { bw_pages: usize, color_pages: usize },
+    ColorAnalysis {
+        bw_pages: usize,
+        color_pages: usize,
+        /// 1-based page numbers of the color pages, as shown in PDF viewers
+        color_page_numbers: Vec<usize>,
+        /// Fraction of sampled pixels in color on each color page, matching
+        /// `color_page_numbers`
+        color_page_coverage: Vec<f32>,
+        /// Average color coverage over all analyzed pages, 0.0 to 1.0
+        avg_color_coverage: f32,
+        max_color_coverage: f32,
+    },
+    /// `ColorAnalysis` with the result of every page, when enabled in the settings
+    ColorAnalysisDetailed {
+        bw_pages: usize,
+        color_pages: usize,
+        color_page_numbers: Vec<usize>,
+        color_page_coverage: Vec<f32>,
+        avg_color_coverage: f32,
+        max_color_coverage: f32,
+        /// 1-based page number and whether the page is in color, for each analyzed page
+        page_results: Vec<(usize, bool)>,
+    },
+    ImageAnalysis {
+        total_images: usize,
+        below_threshold: usize,
+        min_dpi: Option<f32>,
+        compression_types: Vec<String>,
+        /// 1-based pages with low-DPI images and the lowest DPI on each
+        low_dpi_pages: Vec<(usize, f32)>,
+    },
+    PageSizeAnalysis {
+        /// Distinct page sizes in points, rounded to 1pt
+        sizes: Vec<(f32, f32)>,
+        /// Page count per standard paper size name, "Other" for non-standard sizes
+        named_sizes: Vec<(String, usize)>,
+        has_mixed_sizes: bool,
+        has_mixed_orientations: bool,
+        dominant_size: Option<(f32, f32)>,
+    },
+    SignatureAnalysis {
+        has_signatures: bool,
+        signature_count: usize,
+        is_certified: bool,
+    },
+    SecurityAnalysis {
+        is_encrypted: bool,
+        requires_password: bool,
+        permissions: Option<u32>,
+    },
+    FormAnalysis {
+        has_acroform: bool,
+        field_count: usize,
+        has_xfa: bool,
+        required_fields: usize,
+    },
+    EmbeddedFilesAnalysis {
+        count: usize,
+        total_size_bytes: u64,
+        filenames: Vec<String>,
+    },
+    PdfVersion { version: String },
+    BlankPages {
+        blank_count: usize,
+        /// 1-based page numbers of the blank pages
+        blank_page_indices: Vec<usize>,
+    },
+    TextContent {
+        total_chars: usize,
+        total_words: usize,
+        has_text_layer: bool,
+        scanned_pages: usize,
+    },
+    HyperlinkAnalysis {
+        total_links: usize,
+        /// Capped at `hyperlink_analysis::MAX_UNIQUE_URLS`
+        unique_urls: Vec<String>,
+        broken_format_count: usize,
+    },
+    JavaScriptAnalysis {
+        has_javascript: bool,
+        script_count: usize,
+        /// The file has compressed object streams, whose actions aren't
+        /// counted, so `has_javascript: false` isn't conclusive
+        #[serde(default)]
+        unscanned_object_streams: bool,
+    },
+    InkCoverage {
+        /// Average fraction of non-white pixels, 0.0 (blank) to 1.0 (full bleed)
+        avg_coverage: f64,
+        /// Average C, M, Y, K ink amounts estimated from the rendered RGB
+        avg_cmyk: [f64; 4],
+        per_page: Vec<f64>,
+        /// 1-based page number of the first `per_page` entry
+        first_page: usize,
+    },
+    LayerAnalysis {
+        has_layers: bool,
+        layer_names: Vec<String>,
+        visible_count: usize,
+        hidden_count: usize,
+        /// The file has compressed object streams, whose layers aren't found
+        #[serde(default)]
+        unscanned_object_streams: bool,
+    },
+    ColorSpaceAnalysis {
+        has_cmyk: bool,
+        has_rgb: bool,
+        has_spot_colors: bool,
+        spot_color_names: Vec<String>,
+        /// Analyzed pages with images in each color space; a page can count
+        /// for several. Vector and text colors aren't known per page.
+        #[serde(default)]
+        rgb_pages: usize,
+        /// 1-based page numbers of the analyzed pages with RGB images
+        #[serde(default)]
+        rgb_page_numbers: Vec<usize>,
+        #[serde(default)]
+        cmyk_pages: usize,
+        #[serde(default)]
+        gray_pages: usize,
+    },
+    TransparencyAnalysis {
+        has_transparency: bool,
+        transparent_page_count: usize,
+        has_overprint: bool,
+        /// The file has compressed object streams, whose graphics states
+        /// aren't checked for overprint
+        #[serde(default)]
+        unscanned_object_streams: bool,
+    },
+    PageRotationAnalysis {
+        rotated_page_count: usize,
+        /// (1-based page number, clockwise degrees) of pages with a non-zero /Rotate
+        rotations: Vec<(usize, i32)>,
+    },
+    /// Page counts by visual orientation, taking /Rotate into account
+    Orientation {
+        portrait: usize,
+        landscape: usize,
+    },
+    /// Size of the whole file on disk, also per page
+    FileSize {
+        bytes: u64,
+        bytes_per_page: u64,
+    },
+    /// Hex encoded SHA-256 of the file's bytes
+    FileHash {
+        sha256: String,
+    },
+    /// Annotations on the analyzed pages, popups excluded
+    Annotations {
+        total: usize,
+        /// Count per subtype, most frequent first
+        by_type: Vec<(String, usize)>,
+        form_fields: usize,
+    },
+    /// Bookmark tree of the whole document; `max_depth` is 1 when all
+    /// bookmarks are top-level
+    Outline {
+        total: usize,
+        top_level: usize,
+        max_depth: usize,
+    },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PdfAnalysisResult {
     pub filename: String,
     pub path: String,
     pub results: Vec<AnalysisResult>,
+    /// Analyzer failures and problems found in the results
+    #[serde(default)]
     pub errors: Vec<String>,
+    /// Milliseconds each analyzer took on this file, by analyzer name
+    #[serde(default)]
+    pub analyzer_timings: Vec<(String, u64)>,
+    /// Pages the analysis was limited to, `None` for the whole document
+    #[serde(default)]
+    pub page_range: Option<PageRange>,
+}
+
+impl PdfAnalysisResult {
+    /// Whether the 1-based page `number` was part of the analysis.
+    pub fn analyzed_page(&self, number: usize) -> bool {
+        self.page_range
+            .is_none_or(|range| (range.first..=range.last).contains(&number))
+    }
+}
+
+/// Pages of a PDF selected for analysis, 1-based and inclusive as shown to the user
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PageRange {
+    pub first: usize,
+    pub last: usize,
+}
+
+impl PageRange {
+    /// 0-based page indices in a document with `page_count` pages. Pages
+    /// outside the document are clamped off instead of failing.
+    pub fn indices(&self, page_count: usize) -> Range<usize> {
+        let start = self.first.saturating_sub(1).min(page_count);
+        let end = self.last.min(page_count).max(start);
+        start..end
+    }
+}
+
+/// The pages of `document` with 0-based indices in `pages`, together with
+/// their index. Pages pdfium fails to load are skipped.
+pub fn selected_pages<'a, 'b>(
+    document: &'b PdfDocument<'a>,
+    pages: Range<usize>,
+) -> impl Iterator<Item = (usize, PdfPage<'a>)> + 'b {
+    pages.filter_map(move |index| {
+        document
+            .pages()
+            .get(index as u16)
+            .ok()
+            .map(|page| (index, page))
+    })
+}
+
+/// Stops a running analysis batch. Clones share the same flag, so the UI
+/// keeps one and hands clones to the queued jobs.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
 }
 
 pub trait Analyzer: Send + Sync {
     fn id(&self) -> &'static str;
     fn name(&self) -> &'static str;
-    fn analyze(&self, document: &PdfDocument, path: &Path) -> Result<AnalysisResult>;
+    /// Analyze the document, looking only at the pages with 0-based indices
+    /// in `pages` where the analysis is per page.
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult>;
+    /// Like `analyze`, reporting `(pages_done, pages_total)` for analyzers that
+    /// work through the document page by page. These stop with
+    /// `AppError::Cancelled` between pages once `cancel` is set.
+    fn analyze_with_progress(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+        _progress: &dyn Fn(usize, usize),
+        _cancel: &CancellationToken,
+    ) -> Result<AnalysisResult> {
+        self.analyze(document, path, pages)
+    }
+    /// Problems in a successful result that should still be reported as
+    /// errors, e.g. content the user configured as unacceptable.
+    fn result_errors(&self, _result: &AnalysisResult) -> Vec<String> {
+        vec![]
+    }
     fn config_params(&self) -> Vec<ConfigParam> {
         vec![]
     }
@@ -34,32 +318,42 @@ pub trait Analyzer: Send + Sync {
 
 pub struct AnalyzerRegistry {
     analyzers: Vec<Box<dyn Analyzer>>,
+    /// Enabled flags by analyzer id from the config, missing ones are enabled
+    enabled: HashMap<String, bool>,
 }
 
 impl AnalyzerRegistry {
     pub fn new() -> Self {
-        Self { analyzers: vec![] }
+        Self {
+            analyzers: vec![],
+            enabled: HashMap::new(),
+        }
     }
 
     pub fn register(&mut self, analyzer: Box<dyn Analyzer>) {
         self.analyzers.push(analyzer);
     }
 
-    pub fn analyzers(&self) -> &[Box<dyn Analyzer>] {
-        &self.analyzers
+    /// Analyzers not switched off in the config, in registration order.
+    pub fn enabled_analyzers(&self) -> impl Iterator<Item = &Box<dyn Analyzer>> {
+        self.analyzers
+            .iter()
+            .filter(|a| self.enabled.get(a.id()).copied().unwrap_or(true))
     }
 
     pub fn apply_config(&mut self, config: &Config) {
+        self.enabled = config.enabled.clone();
         for analyzer in &mut self.analyzers {
             analyzer.apply_config(config);
         }
     }
 
+    /// Settings of every analyzer, including those without parameters so
+    /// they can still be switched on and off.
     pub fn all_config_params(&self) -> Vec<(&'static str, &'static str, Vec<ConfigParam>)> {
         self.analyzers
             .iter()
             .map(|a| (a.id(), a.name(), a.config_params()))
-            .filter(|(_, _, params)| !params.is_empty())
             .collect()
     }
 }
@@ -68,7 +362,28 @@ impl Default for AnalyzerRegistry {
     fn default() -> Self {
         let mut registry = Self::new();
         registry.register(Box::new(page_count::PageCountAnalyzer));
-        registry.register(Box::new(color_analysis::ColorAnalysisAnalyzer));
+        registry.register(Box::new(color_analysis::ColorAnalysisAnalyzer::default()));
+        registry.register(Box::new(image_analysis::ImageAnalyzer::default()));
+        registry.register(Box::new(page_size::PageSizeAnalyzer::default()));
+        registry.register(Box::new(signature_analysis::SignatureAnalyzer));
+        registry.register(Box::new(security_analysis::SecurityAnalyzer));
+        registry.register(Box::new(form_analysis::FormAnalyzer));
+        registry.register(Box::new(embedded_files::EmbeddedFilesAnalyzer));
+        registry.register(Box::new(pdf_version::PdfVersionAnalyzer));
+        registry.register(Box::new(blank_pages::BlankPageAnalyzer::default()));
+        registry.register(Box::new(text_content::TextContentAnalyzer::default()));
+        registry.register(Box::new(hyperlink_analysis::HyperlinkAnalyzer));
+        registry.register(Box::new(javascript_analysis::JavaScriptAnalyzer::default()));
+        registry.register(Box::new(ink_coverage::InkCoverageAnalyzer::default()));
+        registry.register(Box::new(layer_analysis::LayerAnalyzer::default()));
+        registry.register(Box::new(color_space::ColorSpaceAnalyzer));
+        registry.register(Box::new(transparency::TransparencyAnalyzer::default()));
+        registry.register(Box::new(page_rotation::PageRotationAnalyzer::default()));
+        registry.register(Box::new(orientation::OrientationAnalyzer));
+        registry.register(Box::new(file_size::FileSizeAnalyzer));
+        registry.register(Box::new(file_hash::FileHashAnalyzer));
+        registry.register(Box::new(annotations::AnnotationAnalyzer));
+        registry.register(Box::new(outline::OutlineAnalyzer));
         registry
     }
 }
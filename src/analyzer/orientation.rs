@@ -0,0 +1,59 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::error::Result;
+
+pub struct OrientationAnalyzer;
+
+impl Analyzer for OrientationAnalyzer {
+    fn id(&self) -> &'static str {
+        "orientation"
+    }
+
+    fn name(&self) -> &'static str {
+        "Page Orientation"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut portrait = 0;
+        let mut landscape = 0;
+
+        for (_, page) in selected_pages(document, pages) {
+            let (width, height) = match page.boundaries().media() {
+                Ok(media) => (media.bounds.width().value, media.bounds.height().value),
+                Err(_) => (page.width().value, page.height().value),
+            };
+
+            // A quarter turn shows the media box sideways, so classify what the reader sees
+            let quarter_turn = matches!(
+                page.rotation(),
+                Ok(PdfPageRenderRotation::Degrees90 | PdfPageRenderRotation::Degrees270)
+            );
+            let (width, height) = if quarter_turn {
+                (height, width)
+            } else {
+                (width, height)
+            };
+
+            // Square pages count as portrait, as in the page size analysis
+            if width > height {
+                landscape += 1;
+            } else {
+                portrait += 1;
+            }
+        }
+
+        Ok(AnalysisResult::Orientation {
+            portrait,
+            landscape,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+
+/// Size and depth of the bookmark tree, for checking that long documents can
+/// be navigated. Documents without an outline report zeros.
+pub struct OutlineAnalyzer;
+
+/// Bookmarks counted while walking the tree. Malformed outlines can loop back
+/// on themselves, so the walk stops after `limit` bookmarks.
+struct Walk {
+    limit: usize,
+    total: usize,
+    max_depth: usize,
+}
+
+impl Walk {
+    /// Count `first`, its siblings and all of their descendants at `depth`.
+    fn visit(&mut self, first: Option<PdfBookmark>, depth: usize) {
+        let mut bookmark = first;
+        while let Some(current) = bookmark {
+            if self.total >= self.limit {
+                return;
+            }
+            self.total += 1;
+            self.max_depth = self.max_depth.max(depth);
+            self.visit(current.first_child(), depth + 1);
+            bookmark = current.next_sibling();
+        }
+    }
+}
+
+impl Analyzer for OutlineAnalyzer {
+    fn id(&self) -> &'static str {
+        "outline"
+    }
+
+    fn name(&self) -> &'static str {
+        "Outline"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let bookmarks = document.bookmarks();
+        // pdfium-render's iterator skips bookmarks it has already visited
+        let limit = bookmarks.iter().count();
+
+        let mut top_level = 0;
+        let mut bookmark = bookmarks.root();
+        while let Some(current) = bookmark {
+            if top_level >= limit {
+                break;
+            }
+            top_level += 1;
+            bookmark = current.next_sibling();
+        }
+
+        let mut walk = Walk {
+            limit,
+            total: 0,
+            max_depth: 0,
+        };
+        walk.visit(bookmarks.root(), 1);
+
+        Ok(AnalysisResult::Outline {
+            total: walk.total,
+            top_level,
+            max_depth: walk.max_depth,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::path::Path;
 
 use pdfium_render::prelude::*;
@@ -16,8 +17,13 @@ impl Analyzer for PageCountAnalyzer {
         "Page Count"
     }
 
-    fn analyze(&self, document: &PdfDocument, _path: &Path) -> Result<AnalysisResult> {
-        let total = document.pages().len() as usize;
+    fn analyze(
+        &self,
+        _document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let total = pages.len();
         Ok(AnalysisResult::PageCount { total })
     }
 }
@@ -0,0 +1,88 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+pub struct PageRotationAnalyzer {
+    warn_on_rotation: bool,
+}
+
+impl Default for PageRotationAnalyzer {
+    fn default() -> Self {
+        Self {
+            warn_on_rotation: true,
+        }
+    }
+}
+
+impl Analyzer for PageRotationAnalyzer {
+    fn id(&self) -> &'static str {
+        "page_rotation"
+    }
+
+    fn name(&self) -> &'static str {
+        "Page Rotation"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut rotations = Vec::new();
+
+        for (index, page) in selected_pages(document, pages) {
+            // pdfium normalizes /Rotate to a multiple of 90, including inherited values
+            let degrees = match page.rotation() {
+                Ok(PdfPageRenderRotation::Degrees90) => 90,
+                Ok(PdfPageRenderRotation::Degrees180) => 180,
+                Ok(PdfPageRenderRotation::Degrees270) => 270,
+                _ => continue,
+            };
+            rotations.push((index + 1, degrees));
+        }
+
+        Ok(AnalysisResult::PageRotationAnalysis {
+            rotated_page_count: rotations.len(),
+            rotations,
+        })
+    }
+
+    fn result_errors(&self, result: &AnalysisResult) -> Vec<String> {
+        match result {
+            AnalysisResult::PageRotationAnalysis { rotations, .. }
+                if self.warn_on_rotation && !rotations.is_empty() =>
+            {
+                let pages: Vec<String> = rotations
+                    .iter()
+                    .map(|(page, degrees)| format!("p. {} ({}°)", page, degrees))
+                    .collect();
+                vec![format!("Rotated pages: {}", pages.join(", "))]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "warn_on_rotation",
+            label: "Warn on rotated pages",
+            default: ConfigValue::Bool(true),
+            description:
+                "List rotated pages in the analysis errors so they can be fixed before printing",
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Bool(v)) = config.get_analyzer_value(self.id(), "warn_on_rotation")
+        {
+            self.warn_on_rotation = *v;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+/// Standard paper sizes in millimeters (short edge, long edge).
+const STANDARD_SIZES: &[(&str, f32, f32)] = &[
+    ("A0", 841.0, 1189.0),
+    ("A1", 594.0, 841.0),
+    ("A2", 420.0, 594.0),
+    ("A3", 297.0, 420.0),
+    ("A4", 210.0, 297.0),
+    ("A5", 148.0, 210.0),
+    ("A6", 105.0, 148.0),
+    ("B4", 250.0, 353.0),
+    ("B5", 176.0, 250.0),
+    ("Letter", 215.9, 279.4),
+    ("Legal", 215.9, 355.6),
+    ("Tabloid", 279.4, 431.8),
+    ("Executive", 184.2, 266.7),
+];
+
+pub struct PageSizeAnalyzer {
+    tolerance_mm: f64,
+    size_tolerance_pt: f64,
+}
+
+impl Default for PageSizeAnalyzer {
+    fn default() -> Self {
+        Self {
+            tolerance_mm: 2.0,
+            size_tolerance_pt: 2.0,
+        }
+    }
+}
+
+impl PageSizeAnalyzer {
+    /// Map page dimensions to a standard size name, ignoring orientation.
+    fn classify(&self, width_mm: f32, height_mm: f32) -> &'static str {
+        let short = width_mm.min(height_mm);
+        let long = width_mm.max(height_mm);
+        let tolerance = self.tolerance_mm as f32;
+
+        STANDARD_SIZES
+            .iter()
+            .find(|(_, s, l)| (short - s).abs() <= tolerance && (long - l).abs() <= tolerance)
+            .map(|(name, _, _)| *name)
+            .unwrap_or("Other")
+    }
+
+    /// Whether two page sizes are the same sheet, regardless of orientation.
+    fn same_size(&self, a: (f32, f32), b: (f32, f32)) -> bool {
+        let tolerance = self.size_tolerance_pt as f32;
+        let (a_short, a_long) = (a.0.min(a.1), a.0.max(a.1));
+        let (b_short, b_long) = (b.0.min(b.1), b.0.max(b.1));
+        (a_short - b_short).abs() <= tolerance && (a_long - b_long).abs() <= tolerance
+    }
+}
+
+impl Analyzer for PageSizeAnalyzer {
+    fn id(&self) -> &'static str {
+        "page_size"
+    }
+
+    fn name(&self) -> &'static str {
+        "Page Size"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut named_sizes: Vec<(String, usize)> = Vec::new();
+        // Distinct sizes in points with the number of pages using each
+        let mut size_counts: Vec<((f32, f32), usize)> = Vec::new();
+        let mut has_portrait = false;
+        let mut has_landscape = false;
+
+        for (_, page) in selected_pages(document, pages) {
+            let (width, height) = match page.boundaries().media() {
+                Ok(media) => (media.bounds.width(), media.bounds.height()),
+                Err(_) => (page.width(), page.height()),
+            };
+
+            let name = self.classify(width.to_mm(), height.to_mm());
+            match named_sizes.iter_mut().find(|(n, _)| n == name) {
+                Some((_, count)) => *count += 1,
+                None => named_sizes.push((name.to_string(), 1)),
+            }
+
+            let size = (width.value.round(), height.value.round());
+            match size_counts
+                .iter_mut()
+                .find(|(s, _)| self.same_size(*s, size))
+            {
+                Some((_, count)) => *count += 1,
+                None => size_counts.push((size, 1)),
+            }
+
+            if size.0 > size.1 {
+                has_landscape = true;
+            } else {
+                has_portrait = true;
+            }
+        }
+
+        let dominant_size = size_counts
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(size, _)| *size);
+
+        Ok(AnalysisResult::PageSizeAnalysis {
+            sizes: size_counts.iter().map(|(size, _)| *size).collect(),
+            named_sizes,
+            has_mixed_sizes: size_counts.len() > 1,
+            has_mixed_orientations: has_portrait && has_landscape,
+            dominant_size,
+        })
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![
+            ConfigParam {
+                key: "tolerance_mm",
+                label: "Size tolerance (mm)",
+                default: ConfigValue::Float(2.0),
+                description: "Maximum deviation from a standard paper size to still match it",
+                min: Some(ConfigValue::Float(0.0)),
+                step: Some(0.1),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "size_tolerance_pt",
+                label: "Mixed size tolerance (pt)",
+                default: ConfigValue::Float(2.0),
+                description: "Pages differing by less than this are considered the same size",
+                min: Some(ConfigValue::Float(0.0)),
+                step: Some(0.1),
+                ..ConfigParam::default()
+            },
+        ]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Float(v)) = config.get_analyzer_value(self.id(), "tolerance_mm") {
+            self.tolerance_mm = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_analyzer_value(self.id(), "size_tolerance_pt")
+        {
+            self.size_tolerance_pt = *v;
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::{AppError, Result};
+
+/// Parse a "major.minor" version string such as "1.7".
+pub fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+pub struct PdfVersionAnalyzer;
+
+impl Analyzer for PdfVersionAnalyzer {
+    fn id(&self) -> &'static str {
+        "pdf_version"
+    }
+
+    fn name(&self) -> &'static str {
+        "PDF Version"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let version = match document.version() {
+            PdfDocumentVersion::Pdf1_0 => "1.0".to_string(),
+            PdfDocumentVersion::Pdf1_1 => "1.1".to_string(),
+            PdfDocumentVersion::Pdf1_2 => "1.2".to_string(),
+            PdfDocumentVersion::Pdf1_3 => "1.3".to_string(),
+            PdfDocumentVersion::Pdf1_4 => "1.4".to_string(),
+            PdfDocumentVersion::Pdf1_5 => "1.5".to_string(),
+            PdfDocumentVersion::Pdf1_6 => "1.6".to_string(),
+            PdfDocumentVersion::Pdf1_7 => "1.7".to_string(),
+            PdfDocumentVersion::Pdf2_0 => "2.0".to_string(),
+            // pdfium reports the header as a two-digit number, e.g. 21 for "%PDF-2.1"
+            PdfDocumentVersion::Other(raw) if (10..100).contains(&raw) => {
+                format!("{}.{}", raw / 10, raw % 10)
+            }
+            PdfDocumentVersion::Other(raw) => {
+                return Err(AppError::UnsupportedPdfVersion {
+                    version: raw.to_string(),
+                    path: path.display().to_string(),
+                });
+            }
+            PdfDocumentVersion::Unset => {
+                return Err(AppError::UnsupportedPdfVersion {
+                    version: "unset".to_string(),
+                    path: path.display().to_string(),
+                });
+            }
+        };
+
+        Ok(AnalysisResult::PdfVersion { version })
+    }
+}
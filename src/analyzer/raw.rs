@@ -0,0 +1,219 @@
+//! Helpers for reading structures pdfium doesn't expose straight from the
+//! file bytes. Only uncompressed objects are visible; anything stored in a
+//! compressed object stream is missed.
+
+use std::collections::HashSet;
+
+/// Position of the first `needle` at or after `from`.
+pub fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| pos + from)
+}
+
+/// First non-whitespace position at or after `pos`.
+pub fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// Whether `key` is followed by the token `value`, e.g. `/Type /OCG` or `/OP true`.
+pub fn has_name_entry(body: &[u8], key: &[u8], value: &[u8]) -> bool {
+    let mut from = 0;
+    while let Some(pos) = find(body, key, from) {
+        let start = skip_whitespace(body, pos + key.len());
+        let end = start + value.len();
+        if body.get(start..end) == Some(value)
+            && !body.get(end).is_some_and(|b| b.is_ascii_alphanumeric())
+        {
+            return true;
+        }
+        from = pos + key.len();
+    }
+    false
+}
+
+/// Whether the file stores objects in compressed object streams, which the
+/// helpers here can't see into.
+pub fn has_object_streams(bytes: &[u8]) -> bool {
+    has_name_entry(bytes, b"/Type", b"/ObjStm")
+}
+
+/// Whether the name `name` (e.g. `/DeviceCMYK`) occurs anywhere in `bytes`.
+pub fn contains_name(bytes: &[u8], name: &[u8]) -> bool {
+    let mut from = 0;
+    while let Some(pos) = find(bytes, name, from) {
+        let end = pos + name.len();
+        if !bytes.get(end).is_some_and(|b| b.is_ascii_alphanumeric()) {
+            return true;
+        }
+        from = end;
+    }
+    false
+}
+
+/// Read the name starting at `pos`, decoding `#xx` escapes and dropping the
+/// leading slash. Returns the name and the position after it.
+pub fn read_name(bytes: &[u8], pos: usize) -> Option<(String, usize)> {
+    if bytes.get(pos) != Some(&b'/') {
+        return None;
+    }
+
+    let mut out = Vec::new();
+    let mut i = pos + 1;
+    while let Some(&b) = bytes.get(i) {
+        if b.is_ascii_whitespace() || b"/[]()<>{}%".contains(&b) {
+            break;
+        }
+        let escaped = (b == b'#')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(value) => {
+                out.push(value);
+                i += 3;
+            }
+            None => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    Some((String::from_utf8_lossy(&out).to_string(), i))
+}
+
+/// Decode a PDF text string, which is either UTF-16BE with a BOM or single byte.
+pub fn decode_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        bytes.iter().map(|&b| b as char).collect()
+    }
+}
+
+/// Read the literal `(...)` or hex `<...>` string starting at `pos`.
+pub fn read_string(bytes: &[u8], pos: usize) -> Option<String> {
+    match bytes.get(pos)? {
+        b'(' => {
+            let mut out = Vec::new();
+            let mut depth = 0;
+            let mut i = pos + 1;
+            while let Some(&b) = bytes.get(i) {
+                match b {
+                    b'\\' => {
+                        i += 1;
+                        match bytes.get(i)? {
+                            b'n' => out.push(b'\n'),
+                            b'r' => out.push(b'\r'),
+                            b't' => out.push(b'\t'),
+                            digit @ b'0'..=b'7' => {
+                                // Up to three octal digits
+                                let mut value = (digit - b'0') as u32;
+                                for _ in 0..2 {
+                                    match bytes.get(i + 1) {
+                                        Some(d @ b'0'..=b'7') => {
+                                            value = value * 8 + (d - b'0') as u32;
+                                            i += 1;
+                                        }
+                                        _ => break,
+                                    }
+                                }
+                                out.push(value as u8);
+                            }
+                            &other => out.push(other),
+                        }
+                    }
+                    b'(' => {
+                        depth += 1;
+                        out.push(b);
+                    }
+                    b')' if depth == 0 => return Some(decode_text(&out)),
+                    b')' => {
+                        depth -= 1;
+                        out.push(b);
+                    }
+                    _ => out.push(b),
+                }
+                i += 1;
+            }
+            None
+        }
+        b'<' => {
+            let end = find(bytes, b">", pos)?;
+            let hex: Vec<u8> = bytes[pos + 1..end]
+                .iter()
+                .copied()
+                .filter(|b| b.is_ascii_hexdigit())
+                .collect();
+            let out = hex
+                .chunks(2)
+                .filter_map(|pair| {
+                    let text = std::str::from_utf8(pair).ok()?;
+                    u8::from_str_radix(&format!("{:0<2}", text), 16).ok()
+                })
+                .collect::<Vec<u8>>();
+            Some(decode_text(&out))
+        }
+        _ => None,
+    }
+}
+
+/// Object numbers referenced in the array following `key`, e.g. `/OFF [12 0 R]`.
+pub fn read_ref_array(bytes: &[u8], key: &[u8]) -> Option<HashSet<u32>> {
+    let pos = find(bytes, key, 0)?;
+    let start = skip_whitespace(bytes, pos + key.len());
+    if bytes.get(start) != Some(&b'[') {
+        return None;
+    }
+    let end = find(bytes, b"]", start)?;
+
+    let text = String::from_utf8_lossy(&bytes[start + 1..end]).to_string();
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    Some(
+        tokens
+            .windows(3)
+            .filter(|t| t[2] == "R")
+            .filter_map(|t| t[0].parse().ok())
+            .collect(),
+    )
+}
+
+/// All `N G obj ... endobj` bodies of the file with their object numbers.
+pub fn objects(bytes: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut objects = Vec::new();
+    let mut from = 0;
+
+    while let Some(pos) = find(bytes, b"obj", from) {
+        from = pos + 3;
+
+        // "endobj" and other words ending in "obj" are not object headers
+        if pos == 0 || !bytes[pos - 1].is_ascii_whitespace() {
+            continue;
+        }
+
+        let header = String::from_utf8_lossy(&bytes[pos.saturating_sub(24)..pos]).to_string();
+        let mut parts = header.split_whitespace().rev();
+        let (Some(generation), Some(number)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let (Ok(_), Ok(number)) = (generation.parse::<u32>(), number.parse::<u32>()) else {
+            continue;
+        };
+
+        let end = find(bytes, b"endobj", from).unwrap_or(bytes.len());
+        objects.push((number, &bytes[from..end]));
+        from = end;
+    }
+
+    objects
+}
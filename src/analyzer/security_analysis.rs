@@ -0,0 +1,57 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+
+pub struct SecurityAnalyzer;
+
+impl SecurityAnalyzer {
+    /// Result for a document pdfium refused to open without a password.
+    /// Used by the worker before the other analyzers would run.
+    pub fn password_required() -> AnalysisResult {
+        AnalysisResult::SecurityAnalysis {
+            is_encrypted: true,
+            requires_password: true,
+            permissions: None,
+        }
+    }
+}
+
+impl Analyzer for SecurityAnalyzer {
+    fn id(&self) -> &'static str {
+        "security_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Security Analysis"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let is_encrypted = !matches!(
+            document.permissions().security_handler_revision(),
+            Ok(PdfSecurityHandlerRevision::Unprotected)
+        );
+
+        let permissions = if is_encrypted {
+            let bindings = document.bindings();
+            let handle = bindings.get_handle_from_document(document);
+            Some(bindings.FPDF_GetDocPermissions(handle) as u32)
+        } else {
+            None
+        };
+
+        Ok(AnalysisResult::SecurityAnalysis {
+            is_encrypted,
+            requires_password: false,
+            permissions,
+        })
+    }
+}
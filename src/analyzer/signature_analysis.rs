@@ -0,0 +1,41 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{AnalysisResult, Analyzer};
+use crate::error::Result;
+
+pub struct SignatureAnalyzer;
+
+impl Analyzer for SignatureAnalyzer {
+    fn id(&self) -> &'static str {
+        "signature_analysis"
+    }
+
+    fn name(&self) -> &'static str {
+        "Signature Analysis"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        _pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let signatures = document.signatures();
+        let signature_count = signatures.len() as usize;
+
+        // A certifying signature carries a DocMDP transform, which pdfium
+        // exposes as the modification detection permission.
+        let is_certified = signatures
+            .iter()
+            .any(|signature| signature.modification_detection_permission().is_ok());
+
+        Ok(AnalysisResult::SignatureAnalysis {
+            has_signatures: signature_count > 0,
+            signature_count,
+            is_certified,
+        })
+    }
+}
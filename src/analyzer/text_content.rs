@@ -0,0 +1,84 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+pub struct TextContentAnalyzer {
+    min_chars_for_text_layer: usize,
+}
+
+impl Default for TextContentAnalyzer {
+    fn default() -> Self {
+        Self {
+            min_chars_for_text_layer: 20,
+        }
+    }
+}
+
+impl Analyzer for TextContentAnalyzer {
+    fn id(&self) -> &'static str {
+        "text_content"
+    }
+
+    fn name(&self) -> &'static str {
+        "Text Content"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        _path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let mut total_chars = 0;
+        let mut total_words = 0;
+        let mut scanned_pages = 0;
+        let mut page_count = 0;
+
+        for (_, page) in selected_pages(document, pages) {
+            page_count += 1;
+
+            // Only the text layer is read; pages are never rendered here
+            let text = page.text().map(|text| text.all()).unwrap_or_default();
+
+            // Whitespace is ignored so layout padding doesn't count as content
+            let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+            total_chars += chars;
+            total_words += text.split_whitespace().count();
+
+            if chars < self.min_chars_for_text_layer {
+                scanned_pages += 1;
+            }
+        }
+
+        Ok(AnalysisResult::TextContent {
+            total_chars,
+            total_words,
+            has_text_layer: scanned_pages < page_count,
+            scanned_pages,
+        })
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "min_chars_for_text_layer",
+            label: "Minimum characters per page",
+            default: ConfigValue::Int(20),
+            description: "Pages with fewer extractable characters are considered scanned",
+            min: Some(ConfigValue::Int(0)),
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Int(v)) =
+            config.get_analyzer_value(self.id(), "min_chars_for_text_layer")
+        {
+            self.min_chars_for_text_layer = (*v).max(0) as usize;
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use pdfium_render::prelude::*;
+
+use super::raw::{has_name_entry, has_object_streams};
+use super::{selected_pages, AnalysisResult, Analyzer};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+
+#[derive(Default)]
+pub struct TransparencyAnalyzer {
+    warn_on_transparency: bool,
+}
+
+impl Analyzer for TransparencyAnalyzer {
+    fn id(&self) -> &'static str {
+        "transparency"
+    }
+
+    fn name(&self) -> &'static str {
+        "Transparency"
+    }
+
+    fn analyze(
+        &self,
+        document: &PdfDocument,
+        path: &Path,
+        pages: Range<usize>,
+    ) -> Result<AnalysisResult> {
+        let bindings = document.bindings();
+
+        // pdfium flags a page when any of its graphics states has a constant
+        // alpha below 1.0, a soft mask or a blend mode other than /Normal.
+        let transparent_page_count = selected_pages(document, pages)
+            .filter(|(_, page)| {
+                bindings
+                    .is_true(bindings.FPDFPage_HasTransparency(bindings.get_handle_from_page(page)))
+            })
+            .count();
+
+        // Overprint isn't exposed by pdfium, so look for /OP or /op set in an ExtGState
+        let bytes = fs::read(path)?;
+        let has_overprint =
+            has_name_entry(&bytes, b"/OP", b"true") || has_name_entry(&bytes, b"/op", b"true");
+
+        Ok(AnalysisResult::TransparencyAnalysis {
+            has_transparency: transparent_page_count > 0,
+            transparent_page_count,
+            has_overprint,
+            unscanned_object_streams: has_object_streams(&bytes),
+        })
+    }
+
+    fn result_errors(&self, result: &AnalysisResult) -> Vec<String> {
+        match result {
+            AnalysisResult::TransparencyAnalysis {
+                has_transparency: true,
+                transparent_page_count,
+                ..
+            } if self.warn_on_transparency => {
+                vec![format!(
+                    "{} pages use transparency and may need flattening",
+                    transparent_page_count
+                )]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "warn_on_transparency",
+            label: "Warn on transparency",
+            default: ConfigValue::Bool(false),
+            description: "Report PDFs using transparency in the analysis errors",
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Bool(v)) =
+            config.get_analyzer_value(self.id(), "warn_on_transparency")
+        {
+            self.warn_on_transparency = *v;
+        }
+    }
+}
@@ -1,21 +1,30 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
+use std::time::{Duration, Instant};
 
-use crossbeam_channel as chan;
 use egui::TextureHandle;
+use image::RgbaImage;
 
-use crate::analyzer::{AnalyzerRegistry, PdfAnalysisResult};
-use crate::config::Config;
-use crate::error::Result;
-use crate::output::{OutputData, OutputRegistry};
-use crate::pdf::service::{PdfSerivceRequest, PdfiumService};
-use crate::pdf::PdfFile;
+use crate::analyzer::{AnalyzerRegistry, CancellationToken, PageRange, PdfAnalysisResult};
+use crate::config::{AppConfig, Config};
+use crate::error::{AppError, Result};
+use crate::export::AnalysisRun;
+use crate::output::{OutputData, OutputRegistry, OutputRow};
+use crate::pdf::service::{AnalysisJob, BatchEvent, PdfiumService};
+use crate::pdf::worker::PdfWorkerPool;
+use crate::pdf::{PageDetails, PdfFile, ThumbnailSize};
+use crate::watch::FolderWatcher;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppTab {
     PdfList,
     Results,
+    /// Per-page view of the entry of `analysis_results` at this index
+    FileDetail(usize),
+    /// Outputs of the saved snapshot next to those of the current results
+    Compare,
 }
 
 #[derive(Debug, Clone)]
@@ -31,34 +40,297 @@ pub struct AnalysisProgress {
     pub current_analyzer: String,
     pub files_done: usize,
     pub files_total: usize,
+    /// Page progress within the current analyzer, 0 of 0 when not reported
+    pub page_done: usize,
+    pub page_total: usize,
 }
 
 pub enum AnalysisMessage {
     Progress(AnalysisProgress),
     Complete(Vec<PdfAnalysisResult>),
+    /// The batch was cancelled; the results of the files finished before
+    Cancelled(Vec<PdfAnalysisResult>),
     Error(String),
 }
 
+pub enum FolderScanMessage {
+    /// A directory is being read
+    Progress(PathBuf),
+    Found(PathBuf),
+    Error(String),
+    Done,
+}
+
+/// State of a running "Add Folder" scan
+pub struct FolderScan {
+    pub receiver: Receiver<FolderScanMessage>,
+    pub directory: PathBuf,
+    pub found: usize,
+}
+
 pub struct LoadedPdf {
     pub file: PdfFile,
     pub texture: Option<TextureHandle>,
+    /// Non-fatal problem shown next to the entry, e.g. an encrypted file
+    pub warning: Option<String>,
+    /// Password the file was opened with, reused for the analysis
+    pub password: Option<String>,
+    /// Pages to analyze, the whole document if `None`
+    pub page_range: Option<PageRange>,
+    pub thumbnail_status: ThumbnailStatus,
+    pub validation: ValidationStatus,
+}
+
+/// First-page thumbnail of a listed PDF, rendered by a worker once the
+/// entry is scrolled into view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThumbnailStatus {
+    NotRequested,
+    Pending,
+    Loaded,
+    /// Couldn't be rendered, e.g. an encrypted file without its password
+    Failed,
+}
+
+/// Outcome of "Validate All", which re-opens the listed files to catch ones
+/// that were moved, deleted or corrupted since they were added
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationStatus {
+    NotValidated,
+    Pending,
+    Valid,
+    /// Couldn't be opened, with the reason
+    Invalid(String),
+}
+
+/// Sort order of a results table, `column` 0 being the file name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSort {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// One output generated for the snapshot and for the current results, rows
+/// matched by filename
+pub struct ComparedOutput {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<ComparedRow>,
+    /// Label with the value before and after, `None` if the snapshot has no such total
+    pub totals: Vec<(String, Option<String>, String)>,
+}
+
+/// Cell values of one file, without the filename; `None` on the side the
+/// file is missing from
+pub struct ComparedRow {
+    pub filename: String,
+    pub before: Option<Vec<String>>,
+    pub after: Option<Vec<String>>,
+}
+
+/// How long a toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// Toasts fade out over the end of their duration
+pub const TOAST_FADE: Duration = Duration::from_millis(500);
+
+/// Short notification shown in the bottom-right corner until it expires
+pub struct Toast {
+    pub message: String,
+    pub expires_at: Instant,
+    pub kind: ToastKind,
+}
+
+/// Sets the color of a toast: green, yellow or red
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Warn,
+    Error,
+}
+
+/// Size pages are rendered at for the preview window
+const PREVIEW_SIZE: ThumbnailSize = ThumbnailSize {
+    width: 800,
+    max_height: 1100,
+};
+
+/// Preview window paging through one PDF. Pages are rendered by the Pdfium worker
+/// when first shown and kept while the window is open.
+pub struct PdfPreview {
+    pub path: PathBuf,
+    pub password: Option<String>,
+    pub filename: String,
+    pub page_count: usize,
+    /// 0-based index of the shown page
+    pub page: usize,
+    /// Rendered pages waiting to be turned into textures
+    pub rendered: HashMap<usize, RgbaImage>,
+    pub textures: HashMap<usize, TextureHandle>,
+    pub errors: HashMap<usize, String>,
+    requested: HashSet<usize>,
+    sender: crossbeam_channel::Sender<(usize, Result<RgbaImage>)>,
+    receiver: crossbeam_channel::Receiver<(usize, Result<RgbaImage>)>,
+}
+
+/// Size pages are rendered at for the file detail view
+const DETAIL_THUMBNAIL_SIZE: ThumbnailSize = ThumbnailSize {
+    width: 80,
+    max_height: 110,
+};
+
+/// State of the file detail tab. Page details are read by a worker when the
+/// tab opens, page thumbnails when they are scrolled into view.
+pub struct FileDetail {
+    pub path: PathBuf,
+    password: Option<String>,
+    /// `None` until the worker has read the pages
+    pub pages: Option<std::result::Result<Vec<PageDetails>, String>>,
+    /// Rendered pages waiting to be turned into textures
+    pub rendered: HashMap<usize, RgbaImage>,
+    pub textures: HashMap<usize, TextureHandle>,
+    /// Pages whose thumbnail couldn't be rendered
+    pub failed: HashSet<usize>,
+    requested: HashSet<usize>,
+    pages_receiver: crossbeam_channel::Receiver<Result<Vec<PageDetails>>>,
+    sender: crossbeam_channel::Sender<(usize, Result<RgbaImage>)>,
+    receiver: crossbeam_channel::Receiver<(usize, Result<RgbaImage>)>,
+}
+
+/// A PDF being opened by a worker before it is listed
+pub struct PendingLoad {
+    pub path: PathBuf,
+    /// Set once the worker is done; files are listed in the order they were added
+    result: Option<Result<PdfFile>>,
+}
+
+/// Password dialog state for an encrypted PDF waiting to be added
+pub struct PasswordPrompt {
+    pub path: PathBuf,
+    pub password: String,
+    /// Try this password on encrypted files added later in this session
+    pub remember: bool,
+    pub error: Option<String>,
+    /// Password a worker is trying on the file, the prompt waits for the outcome
+    pub checking: Option<PasswordSource>,
+    /// Session passwords not tried on this file yet
+    untried: Vec<String>,
+}
+
+impl PasswordPrompt {
+    /// Have a worker try the next remembered session password on the file.
+    /// The prompt is shown to the user once none are left.
+    fn try_session_password(
+        &mut self,
+        pdf_service: &PdfiumService,
+        done: &crossbeam_channel::Sender<(PathBuf, String, Result<PdfFile>)>,
+    ) {
+        match self.untried.pop() {
+            Some(password) => {
+                self.checking = Some(PasswordSource::Session);
+                pdf_service.queue_unlock(self.path.clone(), password, done.clone());
+            }
+            None => self.checking = None,
+        }
+    }
+}
+
+/// Where the password being tried on a prompted PDF came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasswordSource {
+    /// Remembered this session; on failure the next one is tried
+    Session,
+    /// Entered in the prompt; on failure the prompt shows the error
+    Entered,
 }
 
 pub struct App {
     pub state: AppState,
     pub current_tab: AppTab,
     pub pdfs: Vec<LoadedPdf>,
+    /// Indices into `pdfs` checked for "Analyze Selected", all are analyzed
+    /// when empty
+    pub selected: HashSet<usize>,
+    /// Row last clicked in the PDF list, removed with the Delete key
+    pub current_pdf: Option<usize>,
+    pub app_config: AppConfig,
+    /// Settings of the active profile, written back to `app_config` on save
     pub config: Config,
     pub analyzer_registry: AnalyzerRegistry,
     pub output_registry: OutputRegistry,
     pub progress: Option<AnalysisProgress>,
     pub analysis_results: Vec<PdfAnalysisResult>,
+    /// When the last analysis finished, shown in the status bar
+    pub last_run: Option<chrono::DateTime<chrono::Local>>,
+    pub analysis_started_at: Option<Instant>,
+    /// `None` while the analysis is running
+    pub analysis_finished_at: Option<Instant>,
     pub output_data: Vec<OutputData>,
+    /// Sort order of each output's table by output title; unsorted tables
+    /// keep the PDF list order
+    pub table_sort: HashMap<String, TableSort>,
+    /// Only results whose filename contains this (case-insensitive) are shown
+    /// and counted in the outputs
+    pub results_filter: String,
+    /// Results loaded from an earlier JSON export to compare against, empty
+    /// when not comparing
+    pub comparison_baseline: Vec<PdfAnalysisResult>,
+    /// Results saved with "Save Snapshot" and the current results they are
+    /// compared with in the Compare tab
+    pub comparison_results: Option<(Vec<PdfAnalysisResult>, Vec<PdfAnalysisResult>)>,
+    /// Outputs of `comparison_results`, regenerated with the outputs
+    pub comparison: Vec<ComparedOutput>,
     pub show_settings: bool,
+    /// Name being entered in the "New Profile" dialog, `None` while closed
+    pub new_profile_name: Option<String>,
     pub errors: Vec<String>,
+    /// Directory of the last saved result file, reused for the next save dialog
+    pub last_save_directory: Option<PathBuf>,
+    /// Encrypted PDFs waiting for a password, the first one is being prompted for
+    pub password_prompts: VecDeque<PasswordPrompt>,
+    /// Added PDFs still being opened, in the order they were added
+    pub pending_loads: VecDeque<PendingLoad>,
+    /// Passwords remembered for this session, never written to disk
+    pub session_passwords: Vec<String>,
+    /// Directory tree being searched for PDFs to add
+    pub folder_scan: Option<FolderScan>,
+    /// Background watcher of `app_config.watch_folder`, running while enabled
+    pub watcher: Option<FolderWatcher>,
+    /// PDFs dropped during an analysis, added once it finishes
+    pub queued_pdfs: Vec<PathBuf>,
+    /// Queued PDFs were added after the last analysis and are not in its results
+    pub queued_pdfs_added: bool,
+    pub toasts: Vec<Toast>,
+    /// Files that failed in the running or last analysis
+    pub analysis_failures: usize,
+    /// The last analysis was cancelled, so its results are partial
+    pub analysis_cancelled: bool,
+    /// Page preview window, open while `Some`
+    pub preview: Option<PdfPreview>,
+    /// Open while `current_tab` is `AppTab::FileDetail`
+    pub file_detail: Option<FileDetail>,
 
     // Communication channels
+    pub load_sender: crossbeam_channel::Sender<(PathBuf, Result<PdfFile>)>,
+    /// PDFs opened by the Pdfium worker, by path
+    pub load_receiver: crossbeam_channel::Receiver<(PathBuf, Result<PdfFile>)>,
+    pub thumbnail_sender: crossbeam_channel::Sender<(PathBuf, Result<RgbaImage>)>,
+    /// Thumbnails rendered by the Pdfium worker, by PDF path
+    pub thumbnails_receiver: crossbeam_channel::Receiver<(PathBuf, Result<RgbaImage>)>,
+    pub validation_sender: crossbeam_channel::Sender<(PathBuf, Result<()>)>,
+    /// Validation outcomes by PDF path
+    pub validation_receiver: crossbeam_channel::Receiver<(PathBuf, Result<()>)>,
+    pub unlock_sender: crossbeam_channel::Sender<(PathBuf, String, Result<PdfFile>)>,
+    /// Passwords tried on prompted PDFs by the Pdfium worker, with the PDF path
+    pub unlock_receiver: crossbeam_channel::Receiver<(PathBuf, String, Result<PdfFile>)>,
+    pub reanalysis_sender: crossbeam_channel::Sender<BatchEvent>,
+    /// Single files analyzed again with ↺
+    pub reanalysis_receiver: crossbeam_channel::Receiver<BatchEvent>,
+    /// Files being re-analyzed and their page range, by PDF path
+    pub reanalyzing: HashMap<PathBuf, Option<PageRange>>,
     pub analysis_receiver: Option<Receiver<AnalysisMessage>>,
+    /// Set to stop the running analysis before its next file
+    pub cancel_flag: Option<CancellationToken>,
 
     // pdf service
     pub pdf_service: crate::pdf::service::PdfiumService,
@@ -66,55 +338,843 @@ pub struct App {
 
 impl Default for App {
     fn default() -> Self {
-        let config = Config::load();
+        let app_config = AppConfig::load();
+        let config = app_config.active_config();
         let mut analyzer_registry = AnalyzerRegistry::default();
         let mut output_registry = OutputRegistry::default();
+        let (thumbnail_sender, thumbnails_receiver) = crossbeam_channel::unbounded();
+        let (validation_sender, validation_receiver) = crossbeam_channel::unbounded();
+        let (load_sender, load_receiver) = crossbeam_channel::unbounded();
+        let (unlock_sender, unlock_receiver) = crossbeam_channel::unbounded();
+        let (reanalysis_sender, reanalysis_receiver) = crossbeam_channel::unbounded();
 
         analyzer_registry.apply_config(&config);
         output_registry.apply_config(&config);
 
-        Self {
+        let mut app = Self {
             state: AppState::Ready,
             current_tab: AppTab::PdfList,
             pdfs: Vec::new(),
+            selected: HashSet::new(),
+            current_pdf: None,
+            app_config,
             config,
             analyzer_registry,
             output_registry,
             progress: None,
             analysis_results: Vec::new(),
+            last_run: None,
+            analysis_started_at: None,
+            analysis_finished_at: None,
             output_data: Vec::new(),
+            table_sort: HashMap::new(),
+            results_filter: String::new(),
+            comparison_baseline: Vec::new(),
+            comparison_results: None,
+            comparison: Vec::new(),
             show_settings: false,
+            new_profile_name: None,
             errors: Vec::new(),
+            last_save_directory: None,
+            password_prompts: VecDeque::new(),
+            pending_loads: VecDeque::new(),
+            session_passwords: Vec::new(),
+            folder_scan: None,
+            watcher: None,
+            queued_pdfs: Vec::new(),
+            queued_pdfs_added: false,
+            toasts: Vec::new(),
+            analysis_failures: 0,
+            analysis_cancelled: false,
+            preview: None,
+            file_detail: None,
+            load_sender,
+            load_receiver,
+            thumbnail_sender,
+            thumbnails_receiver,
+            validation_sender,
+            validation_receiver,
+            unlock_sender,
+            unlock_receiver,
+            reanalysis_sender,
+            reanalysis_receiver,
+            reanalyzing: HashMap::new(),
             analysis_receiver: None,
+            cancel_flag: None,
             pdf_service: crate::pdf::service::PdfiumWorker::service().unwrap(),
-        }
+        };
+        app.reject_invalid_options();
+        app.restore_session();
+        app.update_watcher();
+        app
     }
 }
 
 impl App {
+    /// Have a worker open a PDF; it is listed by `update_pending_loads` once
+    /// opened, so adding many files doesn't block the UI.
     pub fn add_pdf(&mut self, path: PathBuf) -> Result<()> {
-        let file = self.pdf_service.load_pdf(path)?;
+        self.check_already_listed(&path)?;
+        self.pdf_service
+            .queue_load(path.clone(), None, self.load_sender.clone());
+        self.pending_loads
+            .push_back(PendingLoad { path, result: None });
+        Ok(())
+    }
+
+    /// List the PDFs opened since the last frame, keeping the order they were added in.
+    pub fn update_pending_loads(&mut self) {
+        while let Ok((path, result)) = self.load_receiver.try_recv() {
+            // The list may have been cleared while the file was opened
+            if let Some(pending) = self
+                .pending_loads
+                .iter_mut()
+                .find(|pending| pending.path == path && pending.result.is_none())
+            {
+                pending.result = Some(result);
+            }
+        }
+
+        while self
+            .pending_loads
+            .front()
+            .is_some_and(|pending| pending.result.is_some())
+        {
+            let Some(PendingLoad {
+                path,
+                result: Some(result),
+            }) = self.pending_loads.pop_front()
+            else {
+                break;
+            };
+            if let Err(e) = self.list_loaded_pdf(path, result) {
+                self.errors.push(e.to_string());
+            }
+        }
+    }
+
+    pub fn is_loading_pdfs(&self) -> bool {
+        !self.pending_loads.is_empty()
+    }
+
+    /// Add an opened PDF to the list. Encrypted files are opened with a
+    /// remembered session password if one fits, otherwise they are queued
+    /// for the password prompt.
+    fn list_loaded_pdf(&mut self, path: PathBuf, result: Result<PdfFile>) -> Result<()> {
+        match result {
+            Ok(file) => {
+                self.check_duplicate(&path, file.content_hash.as_deref())?;
+                self.push_pdf(file, None);
+                Ok(())
+            }
+            Err(AppError::PasswordRequired { .. }) => {
+                // Checked before prompting so a copy doesn't ask for its password again
+                self.check_duplicate(&path, crate::pdf::hash_file(&path).ok().as_deref())?;
+                let mut prompt = PasswordPrompt {
+                    path,
+                    password: String::new(),
+                    remember: true,
+                    error: None,
+                    checking: None,
+                    untried: self.session_passwords.clone(),
+                };
+                prompt.try_session_password(&self.pdf_service, &self.unlock_sender);
+                self.password_prompts.push_back(prompt);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fail if the file is already listed or waiting for its password, also
+    /// when reached through a different path like `./a.pdf` or a symlinked folder.
+    fn check_already_listed(&self, path: &Path) -> Result<()> {
+        let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+        let new_path = canonical(path);
+        let listed = self
+            .pdfs
+            .iter()
+            .map(|pdf| &pdf.file.path)
+            .chain(self.password_prompts.iter().map(|prompt| &prompt.path))
+            .chain(self.pending_loads.iter().map(|pending| &pending.path));
+
+        // Only files of the same name need to touch the file system
+        for existing in listed.filter(|p| p.file_name() == new_path.file_name()) {
+            if canonical(existing) == new_path {
+                return Err(AppError::AlreadyListed {
+                    path: path.display().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Fail if a PDF with the same content hash is already in the list.
+    fn check_duplicate(&self, path: &Path, content_hash: Option<&str>) -> Result<()> {
+        let Some(hash) = content_hash else {
+            return Ok(());
+        };
+        match self
+            .pdfs
+            .iter()
+            .find(|pdf| pdf.file.content_hash.as_deref() == Some(hash))
+        {
+            Some(existing) => Err(AppError::DuplicatePdf {
+                path: path.display().to_string(),
+                existing: existing.file.filename.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn push_pdf(&mut self, file: PdfFile, password: Option<String>) {
+        // Written to the config file with the session on exit
+        self.app_config.add_recent_file(file.path.clone());
         self.pdfs.push(LoadedPdf {
             file,
             texture: None,
+            warning: None,
+            password,
+            page_range: None,
+            thumbnail_status: ThumbnailStatus::NotRequested,
+            validation: ValidationStatus::NotValidated,
         });
-        Ok(())
+    }
+
+    /// Handle the passwords the Pdfium worker tried since the last frame.
+    pub fn update_unlocks(&mut self) {
+        while let Ok((path, password, result)) = self.unlock_receiver.try_recv() {
+            // The prompt may have been skipped or cleared in the meantime
+            let Some(index) = self
+                .password_prompts
+                .iter()
+                .position(|prompt| prompt.path == path && prompt.checking.is_some())
+            else {
+                continue;
+            };
+
+            let source = self.password_prompts[index].checking;
+            match result {
+                Ok(file) => {
+                    let Some(prompt) = self.password_prompts.remove(index) else {
+                        continue;
+                    };
+                    self.push_pdf(file, Some(password.clone()));
+
+                    if source == Some(PasswordSource::Entered)
+                        && prompt.remember
+                        && !self.session_passwords.contains(&password)
+                    {
+                        self.session_passwords.push(password.clone());
+
+                        // The same password often opens the rest of the batch
+                        for other in self.password_prompts.iter_mut() {
+                            other.untried.push(password.clone());
+                            if other.checking.is_none() {
+                                other.try_session_password(&self.pdf_service, &self.unlock_sender);
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let prompt = &mut self.password_prompts[index];
+                    if source == Some(PasswordSource::Entered) {
+                        prompt.checking = None;
+                        prompt.error = Some(match e {
+                            AppError::PasswordRequired { .. } => "Incorrect password".to_string(),
+                            e => e.to_string(),
+                        });
+                    } else {
+                        prompt.try_session_password(&self.pdf_service, &self.unlock_sender);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_unlocking(&self) -> bool {
+        self.password_prompts
+            .iter()
+            .any(|prompt| prompt.checking.is_some())
+    }
+
+    /// Search a folder and its subfolders for PDFs in the background; they
+    /// are added as they are found.
+    pub fn add_folder(&mut self, folder: PathBuf) {
+        let (tx, rx) = mpsc::channel();
+        let max_depth = self.app_config.max_folder_depth;
+        let root = folder.clone();
+        thread::spawn(move || {
+            scan_folder(&root, max_depth, &tx);
+            let _ = tx.send(FolderScanMessage::Done);
+        });
+
+        self.folder_scan = Some(FolderScan {
+            receiver: rx,
+            directory: folder,
+            found: 0,
+        });
+    }
+
+    /// Add the PDFs found by the folder scan so far.
+    pub fn update_folder_scan(&mut self) {
+        let Some(ref mut scan) = self.folder_scan else {
+            return;
+        };
+
+        let mut found = Vec::new();
+        let mut done = false;
+        while let Ok(msg) = scan.receiver.try_recv() {
+            match msg {
+                FolderScanMessage::Progress(directory) => scan.directory = directory,
+                FolderScanMessage::Found(path) => {
+                    scan.found += 1;
+                    found.push(path);
+                }
+                FolderScanMessage::Error(e) => self.errors.push(e),
+                FolderScanMessage::Done => done = true,
+            }
+        }
+
+        for path in found {
+            if let Err(e) = self.add_pdf(path) {
+                self.errors.push(e.to_string());
+            }
+        }
+        if done {
+            self.folder_scan = None;
+        }
+    }
+
+    /// Have a worker retry the prompted PDF with the entered password.
+    pub fn submit_password(&mut self) {
+        let Some(prompt) = self.password_prompts.front_mut() else {
+            return;
+        };
+
+        if prompt.checking.is_some() {
+            return;
+        }
+        // Opened by a worker, the outcome is handled by `update_unlocks`
+        prompt.checking = Some(PasswordSource::Entered);
+        prompt.error = None;
+        self.pdf_service.queue_unlock(
+            prompt.path.clone(),
+            prompt.password.clone(),
+            self.unlock_sender.clone(),
+        );
+    }
+
+    /// Add the prompted PDF without a password; it is listed but can't be analyzed.
+    pub fn skip_password(&mut self) {
+        if let Some(prompt) = self.password_prompts.pop_front() {
+            let warning = AppError::PasswordRequired {
+                path: prompt.path.display().to_string(),
+            }
+            .to_string();
+            self.pdfs.push(LoadedPdf {
+                file: PdfFile::encrypted(prompt.path),
+                texture: None,
+                warning: Some(warning),
+                password: None,
+                page_range: None,
+                // Can't be rendered without the password
+                thumbnail_status: ThumbnailStatus::Failed,
+                validation: ValidationStatus::NotValidated,
+            });
+        }
+    }
+
+    /// Re-add the PDFs that were loaded when the app was last closed.
+    fn restore_session(&mut self) {
+        for path in self.app_config.recent_paths.clone() {
+            if !path.exists() {
+                self.errors
+                    .push(format!("Skipped {}: file no longer exists", path.display()));
+                continue;
+            }
+            if let Err(e) = self.add_pdf(path) {
+                self.errors.push(e.to_string());
+            }
+        }
+    }
+
+    /// Remember the current PDF list so it can be restored on the next start.
+    pub fn save_session(&mut self) {
+        // Files still being opened are kept too
+        self.app_config.recent_paths = self
+            .pdfs
+            .iter()
+            .map(|p| p.file.path.clone())
+            .chain(
+                self.pending_loads
+                    .iter()
+                    .map(|pending| pending.path.clone()),
+            )
+            .collect();
+        if let Err(e) = self.app_config.save() {
+            let message = format!("Failed to save PDF list: {}", e);
+            self.errors.push(message.clone());
+            self.show_toast(ToastKind::Error, message);
+        }
+    }
+
+    /// Save the PDF list once it changed and all files are opened, so a
+    /// failure to save is reported while the window is still open rather
+    /// than only on exit.
+    pub fn save_session_if_changed(&mut self) {
+        if !self.pending_loads.is_empty() {
+            return;
+        }
+        let unchanged = self
+            .pdfs
+            .iter()
+            .map(|p| &p.file.path)
+            .eq(self.app_config.recent_paths.iter());
+        if !unchanged {
+            self.save_session();
+        }
     }
 
     pub fn remove_pdf(&mut self, index: usize) {
         if index < self.pdfs.len() {
+            let mut selected = self.selection_flags();
+            selected.remove(index);
             self.pdfs.remove(index);
+            self.set_selection_flags(selected);
+            self.current_pdf = match self.current_pdf {
+                Some(current) if current == index => None,
+                Some(current) if current > index => Some(current - 1),
+                current => current,
+            };
+        }
+    }
+
+    /// Whether each entry of `pdfs` is selected, to reorder along with it.
+    fn selection_flags(&self) -> Vec<bool> {
+        (0..self.pdfs.len())
+            .map(|index| self.selected.contains(&index))
+            .collect()
+    }
+
+    fn set_selection_flags(&mut self, flags: Vec<bool>) {
+        self.selected = flags
+            .into_iter()
+            .enumerate()
+            .filter(|(_, selected)| *selected)
+            .map(|(index, _)| index)
+            .collect();
+    }
+
+    pub fn toggle_selected(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+    }
+
+    /// Select every listed PDF, or none if all already are.
+    pub fn toggle_select_all(&mut self) {
+        if self.selected.len() == self.pdfs.len() {
+            self.selected.clear();
+        } else {
+            self.selected = (0..self.pdfs.len()).collect();
+        }
+    }
+
+    /// Analyze a single listed PDF again and replace its results, e.g. after
+    /// the file was fixed. The other results are kept as they are.
+    pub fn reanalyze_pdf(&mut self, index: usize) {
+        let Some(pdf) = self.pdfs.get(index) else {
+            return;
+        };
+        if self.reanalyzing.contains_key(&pdf.file.path) {
+            return;
+        }
+
+        self.reanalyzing
+            .insert(pdf.file.path.clone(), pdf.page_range);
+        self.pdf_service.queue_analysis(
+            AnalysisJob {
+                path: pdf.file.path.clone(),
+                password: pdf.password.clone(),
+                page_range: pdf.page_range,
+                config: self.config.clone(),
+                // The file was probably changed, analyze it afresh
+                use_cache: false,
+                cancel: CancellationToken::new(),
+            },
+            self.reanalysis_sender.clone(),
+        );
+    }
+
+    pub fn is_reanalyzing(&self) -> bool {
+        !self.reanalyzing.is_empty()
+    }
+
+    /// Replace the results of the files re-analyzed since the last frame.
+    pub fn update_reanalysis(&mut self) {
+        while let Ok(event) = self.reanalysis_receiver.try_recv() {
+            let BatchEvent::Done {
+                path,
+                result: analysis,
+            } = event
+            else {
+                continue;
+            };
+            let Some(page_range) = self.reanalyzing.remove(&path) else {
+                continue;
+            };
+            let filename = path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            match analysis {
+                Ok(analysis) => {
+                    let mut result: PdfAnalysisResult = analysis.into();
+                    result.page_range = page_range;
+                    self.replace_result(result, &filename);
+                }
+                Err(e) => self
+                    .errors
+                    .push(format!("Failed to analyze {}: {}", filename, e)),
+            }
+        }
+    }
+
+    /// Put the result of a re-analyzed file in place of its old one.
+    fn replace_result(&mut self, result: PdfAnalysisResult, filename: &str) {
+        match self
+            .analysis_results
+            .iter_mut()
+            .find(|r| r.path == result.path)
+        {
+            Some(existing) => *existing = result,
+            None => {
+                // Not part of the last run; keep the results in PDF list order
+                self.analysis_results.push(result);
+                let position = |path: &str| {
+                    self.pdfs
+                        .iter()
+                        .position(|p| p.file.path.display().to_string() == path)
+                };
+                self.analysis_results
+                    .sort_by_key(|r| position(&r.path).unwrap_or(usize::MAX));
+            }
+        }
+
+        self.regenerate_outputs();
+        // A batch started meanwhile shows its results when it finishes
+        if !matches!(self.state, AppState::Analyzing) {
+            self.state = AppState::Results;
+            self.current_tab = AppTab::Results;
+            self.file_detail = None;
+        }
+        self.show_toast(ToastKind::Info, format!("Re-analyzed: {}", filename));
+    }
+
+    pub fn show_toast(&mut self, kind: ToastKind, message: String) {
+        self.toasts.push(Toast {
+            message,
+            expires_at: Instant::now() + TOAST_DURATION,
+            kind,
+        });
+    }
+
+    /// Drop the thumbnails so they are rendered again at the configured size
+    /// once they are shown.
+    pub fn refresh_thumbnails(&mut self) {
+        for pdf in &mut self.pdfs {
+            pdf.file.thumbnail = None;
+            pdf.texture = None;
+            pdf.thumbnail_status = ThumbnailStatus::NotRequested;
+        }
+    }
+
+    /// Have a worker render the thumbnail of a listed PDF in the background.
+    pub fn request_thumbnail(&mut self, index: usize) {
+        let Some(pdf) = self.pdfs.get_mut(index) else {
+            return;
+        };
+        pdf.thumbnail_status = ThumbnailStatus::Pending;
+        self.pdf_service.queue_thumbnail(
+            pdf.file.path.clone(),
+            pdf.password.clone(),
+            self.app_config.thumbnail_size(),
+            self.thumbnail_sender.clone(),
+        );
+    }
+
+    /// Store the thumbnails rendered since the last frame.
+    pub fn update_thumbnails(&mut self) {
+        let mut received = false;
+        while let Ok((path, thumbnail)) = self.thumbnails_receiver.try_recv() {
+            received = true;
+            // The PDF may have been removed while its thumbnail was rendered
+            let Some(pdf) = self.pdfs.iter_mut().find(|pdf| pdf.file.path == path) else {
+                continue;
+            };
+            match thumbnail {
+                Ok(thumbnail) => {
+                    pdf.file.thumbnail = Some(thumbnail);
+                    pdf.texture = None;
+                    pdf.thumbnail_status = ThumbnailStatus::Loaded;
+                }
+                Err(_) => pdf.thumbnail_status = ThumbnailStatus::Failed,
+            }
+        }
+
+        // The HTML report embeds the thumbnails; rebuilding it per thumbnail
+        // re-encodes every image each time, so wait until all are rendered
+        if received && !self.analysis_results.is_empty() && !self.has_pending_thumbnails() {
+            self.regenerate_outputs();
+        }
+    }
+
+    /// Open the preview window on the first page of a listed PDF.
+    pub fn open_preview(&mut self, index: usize) {
+        let Some(pdf) = self.pdfs.get(index) else {
+            return;
+        };
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.preview = Some(PdfPreview {
+            path: pdf.file.path.clone(),
+            password: pdf.password.clone(),
+            filename: pdf.file.filename.clone(),
+            page_count: pdf.file.page_count,
+            page: 0,
+            rendered: HashMap::new(),
+            textures: HashMap::new(),
+            errors: HashMap::new(),
+            requested: HashSet::new(),
+            sender,
+            receiver,
+        });
+    }
+
+    /// Collect rendered preview pages and request the shown page if needed.
+    pub fn update_preview(&mut self) {
+        let Some(ref mut preview) = self.preview else {
+            return;
+        };
+
+        while let Ok((page, image)) = preview.receiver.try_recv() {
+            match image {
+                Ok(image) => {
+                    preview.rendered.insert(page, image);
+                }
+                Err(e) => {
+                    preview.errors.insert(page, e.to_string());
+                }
+            }
+        }
+
+        if preview.requested.insert(preview.page) {
+            self.pdf_service.queue_page_render(
+                preview.path.clone(),
+                preview.password.clone(),
+                preview.page,
+                PREVIEW_SIZE,
+                preview.sender.clone(),
+            );
+        }
+    }
+
+    /// Whether the preview window waits for the shown page.
+    pub fn is_preview_loading(&self) -> bool {
+        self.preview.as_ref().is_some_and(|preview| {
+            !preview.textures.contains_key(&preview.page)
+                && !preview.errors.contains_key(&preview.page)
+        })
+    }
+
+    /// Show the pages of one analyzed file in the file detail tab.
+    pub fn open_file_detail(&mut self, index: usize) {
+        let Some(result) = self.analysis_results.get(index) else {
+            return;
+        };
+        let path = PathBuf::from(&result.path);
+        let password = self
+            .pdfs
+            .iter()
+            .find(|pdf| pdf.file.path == path)
+            .and_then(|pdf| pdf.password.clone());
+
+        let (pages_sender, pages_receiver) = crossbeam_channel::bounded(1);
+        self.pdf_service
+            .queue_page_details(path.clone(), password.clone(), pages_sender);
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.file_detail = Some(FileDetail {
+            path,
+            password,
+            pages: None,
+            rendered: HashMap::new(),
+            textures: HashMap::new(),
+            failed: HashSet::new(),
+            requested: HashSet::new(),
+            pages_receiver,
+            sender,
+            receiver,
+        });
+        self.current_tab = AppTab::FileDetail(index);
+    }
+
+    pub fn close_file_detail(&mut self) {
+        self.file_detail = None;
+        self.current_tab = AppTab::Results;
+    }
+
+    /// Collect the page details and thumbnails read since the last frame.
+    pub fn update_file_detail(&mut self) {
+        let Some(ref mut detail) = self.file_detail else {
+            return;
+        };
+
+        if let Ok(pages) = detail.pages_receiver.try_recv() {
+            detail.pages = Some(pages.map_err(|e| e.to_string()));
+        }
+        while let Ok((page, image)) = detail.receiver.try_recv() {
+            match image {
+                Ok(image) => {
+                    detail.rendered.insert(page, image);
+                }
+                Err(_) => {
+                    detail.failed.insert(page);
+                }
+            }
+        }
+    }
+
+    /// Have a worker render the thumbnail of a page in the file detail tab.
+    pub fn request_detail_thumbnail(&mut self, page: usize) {
+        let Some(ref mut detail) = self.file_detail else {
+            return;
+        };
+        if detail.requested.insert(page) {
+            self.pdf_service.queue_page_render(
+                detail.path.clone(),
+                detail.password.clone(),
+                page,
+                DETAIL_THUMBNAIL_SIZE,
+                detail.sender.clone(),
+            );
+        }
+    }
+
+    /// Whether the file detail tab waits for its pages or thumbnails.
+    pub fn is_file_detail_loading(&self) -> bool {
+        self.file_detail.as_ref().is_some_and(|detail| {
+            detail.pages.is_none()
+                || detail.requested.len()
+                    > detail.rendered.len() + detail.textures.len() + detail.failed.len()
+        })
+    }
+
+    /// Have the Pdfium worker check that every listed PDF still opens.
+    pub fn validate_all(&mut self) {
+        for pdf in &mut self.pdfs {
+            pdf.validation = ValidationStatus::Pending;
+            self.pdf_service.queue_validation(
+                pdf.file.path.clone(),
+                pdf.password.clone(),
+                self.validation_sender.clone(),
+            );
         }
     }
 
+    /// Store the validation outcomes received since the last frame.
+    pub fn update_validation(&mut self) {
+        while let Ok((path, result)) = self.validation_receiver.try_recv() {
+            // The PDF may have been removed while it was validated
+            let Some(pdf) = self.pdfs.iter_mut().find(|pdf| pdf.file.path == path) else {
+                continue;
+            };
+            pdf.validation = match result {
+                Ok(()) => ValidationStatus::Valid,
+                Err(e) => ValidationStatus::Invalid(e.to_string()),
+            };
+        }
+    }
+
+    pub fn is_validating(&self) -> bool {
+        self.pdfs
+            .iter()
+            .any(|pdf| pdf.validation == ValidationStatus::Pending)
+    }
+
+    pub fn has_pending_thumbnails(&self) -> bool {
+        self.pdfs
+            .iter()
+            .any(|pdf| pdf.thumbnail_status == ThumbnailStatus::Pending)
+    }
+
+    /// Open the next "Add PDFs" or "Add Folder" dialog next to `picked`.
+    pub fn remember_directory(&mut self, picked: Option<&PathBuf>) {
+        let Some(dir) = picked.and_then(|p| p.parent()) else {
+            return;
+        };
+        self.app_config.last_directory = Some(dir.to_path_buf());
+        self.save_config();
+    }
+
+    /// Move a PDF to another position; the next analysis reports it there.
+    pub fn move_pdf(&mut self, from: usize, to: usize) {
+        if from < self.pdfs.len() && to < self.pdfs.len() {
+            let mut selected = self.selection_flags();
+            let flag = selected.remove(from);
+            selected.insert(to, flag);
+            let pdf = self.pdfs.remove(from);
+            self.pdfs.insert(to, pdf);
+            self.set_selection_flags(selected);
+            self.current_pdf = self.current_pdf.map(|current| match current {
+                current if current == from => to,
+                current if from < current && current <= to => current - 1,
+                current if to <= current && current < from => current + 1,
+                current => current,
+            });
+        }
+    }
+
+    /// Sort an output's table by a column, toggling the direction when it is
+    /// already sorted by it.
+    pub fn sort_table(&mut self, title: &str, column: usize) {
+        let ascending = match self.table_sort.get(title) {
+            Some(sort) if sort.column == column => !sort.ascending,
+            _ => true,
+        };
+        self.table_sort
+            .insert(title.to_string(), TableSort { column, ascending });
+    }
+
     pub fn clear(&mut self) {
         self.pdfs.clear();
+        self.selected.clear();
+        self.current_pdf = None;
+        self.pending_loads.clear();
+        // Late answers for these are ignored once their entry is gone
+        self.password_prompts.clear();
+        self.reanalyzing.clear();
+        self.queued_pdfs.clear();
+        self.preview = None;
         self.analysis_results.clear();
+        self.last_run = None;
+        self.analysis_started_at = None;
+        self.analysis_finished_at = None;
+        self.analysis_cancelled = false;
         self.output_data.clear();
+        self.results_filter.clear();
+        self.comparison_baseline.clear();
+        // The snapshot is kept to compare with the files added next
+        if let Some((_, after)) = &mut self.comparison_results {
+            after.clear();
+        }
+        self.update_comparison();
         self.progress = None;
         self.state = AppState::Ready;
         self.current_tab = AppTab::PdfList;
+        self.file_detail = None;
         self.errors.clear();
+        self.queued_pdfs_added = false;
     }
 
     pub fn start_analysis(&mut self) {
@@ -124,103 +1184,618 @@ impl App {
 
         let (progress_tx, progress_rx) = mpsc::channel();
         self.analysis_receiver = Some(progress_rx);
+        let cancel = CancellationToken::new();
+        self.cancel_flag = Some(cancel.clone());
         self.state = AppState::Analyzing;
+        self.queued_pdfs_added = false;
+        self.analysis_started_at = Some(Instant::now());
+        self.analysis_finished_at = None;
+        self.analysis_failures = 0;
+        self.analysis_cancelled = false;
+
+        // Only the checked files with "Analyze Selected", in list order
+        let indices: Vec<usize> = (0..self.pdfs.len())
+            .filter(|index| self.selected.is_empty() || self.selected.contains(index))
+            .collect();
+
+        // Files never scrolled into view still need their thumbnail for the HTML report
+        for &index in &indices {
+            if self.pdfs[index].thumbnail_status == ThumbnailStatus::NotRequested {
+                self.request_thumbnail(index);
+            }
+        }
         self.progress = Some(AnalysisProgress {
             current_file: String::new(),
             current_analyzer: String::new(),
             files_done: 0,
-            files_total: self.pdfs.len(),
+            files_total: indices.len(),
+            page_done: 0,
+            page_total: 0,
         });
 
-        let paths: Vec<PathBuf> = self.pdfs.iter().map(|p| p.file.path.clone()).collect();
+        let paths: Vec<(PathBuf, Option<String>, Option<PageRange>)> = indices
+            .iter()
+            .map(|&index| &self.pdfs[index])
+            .map(|p| (p.file.path.clone(), p.password.clone(), p.page_range))
+            .collect();
         let pdf_service = self.pdf_service.clone();
+        let config = self.config.clone();
+        let use_cache = self.app_config.use_analysis_cache;
+        let workers = self.app_config.worker_count();
 
         thread::spawn(move || {
-            run_analysis(paths, pdf_service, progress_tx);
+            run_analysis(
+                paths,
+                pdf_service,
+                workers,
+                config,
+                use_cache,
+                cancel,
+                progress_tx,
+            );
         });
     }
 
+    /// Ask the running analysis to stop. Results gathered so far are still
+    /// delivered through `AnalysisMessage::Cancelled`.
+    pub fn cancel_analysis(&mut self) {
+        if let Some(ref cancel) = self.cancel_flag {
+            cancel.cancel();
+        }
+    }
+
+    /// Average milliseconds per file of every analyzer that ran, slowest first.
+    pub fn analyzer_timing_averages(&self) -> Vec<(String, f64)> {
+        let mut totals: Vec<(String, u64, usize)> = Vec::new();
+        for result in &self.analysis_results {
+            for (name, ms) in &result.analyzer_timings {
+                match totals.iter_mut().find(|(n, _, _)| n == name) {
+                    Some((_, total, files)) => {
+                        *total += ms;
+                        *files += 1;
+                    }
+                    None => totals.push((name.clone(), *ms, 1)),
+                }
+            }
+        }
+
+        let mut averages: Vec<(String, f64)> = totals
+            .into_iter()
+            .map(|(name, total, files)| (name, total as f64 / files as f64))
+            .collect();
+        averages.sort_by(|a, b| b.1.total_cmp(&a.1));
+        averages
+    }
+
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel_flag
+            .as_ref()
+            .is_some_and(|cancel| cancel.is_cancelled())
+    }
+
     pub fn update_analysis(&mut self) {
         let mut completed = false;
 
         if let Some(ref receiver) = self.analysis_receiver {
             while let Ok(msg) = receiver.try_recv() {
-                match msg {
-                    AnalysisMessage::Progress(progress) => {
+                let (results, cancelled) = match msg {
+                    AnalysisMessage::Progress(mut progress) => {
+                        // The batch's first update is sent after the worker started, so it may carry an older count
+                        if let Some(ref previous) = self.progress {
+                            progress.files_done = progress.files_done.max(previous.files_done);
+                        }
                         self.progress = Some(progress);
-                    }
-                    AnalysisMessage::Complete(results) => {
-                        self.analysis_results = results;
-                        self.output_data =
-                            self.output_registry.generate_all(&self.analysis_results);
-                        self.state = AppState::Results;
-                        self.current_tab = AppTab::Results;
-                        completed = true;
+                        continue;
                     }
                     AnalysisMessage::Error(e) => {
                         self.errors.push(e);
+                        self.analysis_failures += 1;
+                        continue;
                     }
+                    AnalysisMessage::Complete(results) => (results, false),
+                    AnalysisMessage::Cancelled(results) => (results, true),
+                };
+
+                if cancelled {
+                    self.errors
+                        .push(format!("Analysis cancelled after {} files", results.len()));
                 }
+                self.analysis_cancelled = cancelled;
+                self.analysis_results = results;
+                self.last_run = Some(chrono::Local::now());
+                self.analysis_finished_at = Some(Instant::now());
+                self.state = AppState::Results;
+                self.current_tab = AppTab::Results;
+                self.file_detail = None;
+                completed = true;
             }
         }
 
         if completed {
+            let files = self.analysis_results.len();
+            if self.analysis_cancelled {
+                self.show_toast(
+                    ToastKind::Warn,
+                    format!("Analysis cancelled after {} file(s)", files),
+                );
+            } else if files == 0 && self.analysis_failures > 0 {
+                self.show_toast(ToastKind::Error, "⚠ Analysis failed".to_string());
+            } else if self.analysis_failures > 0 {
+                self.show_toast(
+                    ToastKind::Error,
+                    format!("⚠ Analysis failed for {} file(s)", self.analysis_failures),
+                );
+            } else {
+                self.show_toast(
+                    ToastKind::Info,
+                    format!("✓ Analysis complete — {} file(s)", files),
+                );
+            }
+            self.regenerate_outputs();
             self.analysis_receiver = None;
+            self.cancel_flag = None;
+            self.add_queued_pdfs();
+        }
+    }
+
+    /// Add a dropped PDF, or queue it while an analysis is running so the
+    /// list doesn't change under it.
+    pub fn drop_pdf(&mut self, path: PathBuf) -> Result<()> {
+        if matches!(self.state, AppState::Analyzing) {
+            self.queued_pdfs.push(path);
+            Ok(())
+        } else {
+            self.add_pdf(path)
+        }
+    }
+
+    fn add_queued_pdfs(&mut self) {
+        if self.queued_pdfs.is_empty() {
+            return;
+        }
+        for path in std::mem::take(&mut self.queued_pdfs) {
+            if let Err(e) = self.add_pdf(path) {
+                self.errors.push(e.to_string());
+            }
+        }
+        self.queued_pdfs_added = true;
+    }
+
+    /// Start, restart or stop the folder watcher to match the config.
+    pub fn update_watcher(&mut self) {
+        let folder = self
+            .app_config
+            .watch_folder
+            .clone()
+            .filter(|_| self.app_config.watch_enabled);
+
+        if self.watcher.as_ref().map(|w| &w.folder) == folder.as_ref() {
+            return;
+        }
+        self.watcher = None;
+
+        if let Some(folder) = folder {
+            match FolderWatcher::start(
+                folder,
+                self.pdf_service.clone(),
+                self.config.clone(),
+                self.app_config.use_analysis_cache,
+            ) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(e) => self.errors.push(e.to_string()),
+            }
+        }
+    }
+
+    /// Collect problems reported by the folder watcher.
+    pub fn update_watch_errors(&mut self) {
+        if let Some(ref watcher) = self.watcher {
+            self.errors.extend(watcher.errors.try_iter());
         }
     }
 
     pub fn save_config(&mut self) {
-        if let Err(e) = self.config.save() {
+        self.app_config
+            .profiles
+            .insert(self.app_config.active_profile.clone(), self.config.clone());
+        if let Err(e) = self.app_config.save() {
             self.errors.push(format!("Failed to save config: {}", e));
         }
         self.analyzer_registry.apply_config(&self.config);
         self.output_registry.apply_config(&self.config);
     }
+
+    /// Reset settings outside their param's options, e.g. from a hand-edited
+    /// or imported file, and report them.
+    pub fn reject_invalid_options(&mut self) {
+        let rejected = self.config.reject_invalid_options(
+            &self.analyzer_registry.all_config_params(),
+            &self.output_registry.all_config_params(),
+        );
+        if !rejected.is_empty() {
+            self.errors.extend(rejected);
+            self.analyzer_registry.apply_config(&self.config);
+            self.output_registry.apply_config(&self.config);
+        }
+    }
+
+    /// Make another profile active. Existing results are recalculated with
+    /// its output settings, the analyzers apply from the next analysis.
+    pub fn switch_profile(&mut self, name: &str) {
+        let Some(config) = self.app_config.profiles.get(name) else {
+            return;
+        };
+        self.config = config.clone();
+        self.app_config.active_profile = name.to_string();
+        self.reject_invalid_options();
+        self.save_config();
+
+        if !self.analysis_results.is_empty() {
+            self.regenerate_outputs();
+        }
+    }
+
+    /// Rebuild the output tables from the current analysis results.
+    pub fn regenerate_outputs(&mut self) {
+        let thumbnails = self.thumbnail_data_uris();
+        self.output_registry.set_thumbnails(&thumbnails);
+        self.output_registry.set_cancelled(self.analysis_cancelled);
+        let filtered: Vec<PdfAnalysisResult> = self
+            .filtered_result_indices()
+            .into_iter()
+            .map(|index| self.analysis_results[index].clone())
+            .collect();
+        let filter = self.results_filter.to_lowercase();
+        let baseline: Vec<PdfAnalysisResult> = self
+            .comparison_baseline
+            .iter()
+            .filter(|result| result.filename.to_lowercase().contains(&filter))
+            .cloned()
+            .collect();
+        self.output_registry.set_baseline(&baseline);
+        self.output_data = self.output_registry.generate_all(&filtered);
+
+        if let Some((_, after)) = &mut self.comparison_results {
+            *after = self.analysis_results.clone();
+        }
+        self.update_comparison();
+    }
+
+    /// Keep the current results as the "before" side of the Compare tab.
+    pub fn save_snapshot(&mut self) {
+        self.comparison_results =
+            Some((self.analysis_results.clone(), self.analysis_results.clone()));
+        self.update_comparison();
+        self.show_toast(
+            ToastKind::Info,
+            format!(
+                "Snapshot of {} file(s) saved, re-analyze to compare",
+                self.analysis_results.len()
+            ),
+        );
+    }
+
+    pub fn discard_snapshot(&mut self) {
+        self.comparison_results = None;
+        self.comparison.clear();
+        if self.current_tab == AppTab::Compare {
+            self.current_tab = AppTab::Results;
+        }
+    }
+
+    fn update_comparison(&mut self) {
+        let Some((before, after)) = &self.comparison_results else {
+            self.comparison.clear();
+            return;
+        };
+        let before_outputs = self.output_registry.generate_all(before);
+        let after_outputs = self.output_registry.generate_all(after);
+
+        self.comparison = after_outputs
+            .into_iter()
+            .map(|after| {
+                let before = before_outputs.iter().find(|b| b.title == after.title);
+                compare_output(before, &after)
+            })
+            .collect();
+    }
+
+    /// Compare the results with an analysis saved by "Export JSON".
+    pub fn load_comparison(&mut self, path: &Path) -> Result<()> {
+        let baseline = crate::export::json::read_results_json(path)?;
+        if baseline.is_empty() {
+            return Err(AppError::ExportError(format!(
+                "'{}' contains no results",
+                path.display()
+            )));
+        }
+        self.comparison_baseline = baseline;
+        self.regenerate_outputs();
+        Ok(())
+    }
+
+    pub fn clear_comparison(&mut self) {
+        self.comparison_baseline.clear();
+        self.regenerate_outputs();
+    }
+
+    /// Time and duration of the last finished analysis, `None` before the
+    /// first one.
+    pub fn analysis_run(&self) -> Option<AnalysisRun> {
+        let duration = self
+            .analysis_finished_at?
+            .duration_since(self.analysis_started_at?);
+        Some(AnalysisRun {
+            analyzed_at: self
+                .last_run?
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            duration_seconds: duration.as_secs_f64(),
+            cancelled: self.analysis_cancelled,
+        })
+    }
+
+    /// Indices into `analysis_results` of the results matching `results_filter`,
+    /// in the order of their rows in `output_data`.
+    pub fn filtered_result_indices(&self) -> Vec<usize> {
+        let filter = self.results_filter.to_lowercase();
+        self.analysis_results
+            .iter()
+            .enumerate()
+            .filter(|(_, result)| result.filename.to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// First-page thumbnails of the listed PDFs as PNG data URIs by path,
+    /// matching `PdfAnalysisResult::path`.
+    pub fn thumbnail_data_uris(&self) -> HashMap<String, String> {
+        self.pdfs
+            .iter()
+            .filter_map(|pdf| {
+                let thumbnail = pdf.file.thumbnail.as_ref()?;
+                let uri = crate::output::html::png_data_uri(thumbnail)?;
+                Some((pdf.file.path.display().to_string(), uri))
+            })
+            .collect()
+    }
+
+    /// Add a profile starting from the current settings and switch to it.
+    pub fn create_profile(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::ConfigError("Profile name is empty".to_string()));
+        }
+        if self.app_config.profiles.contains_key(name) {
+            return Err(AppError::ConfigError(format!(
+                "Profile '{}' already exists",
+                name
+            )));
+        }
+
+        self.app_config
+            .profiles
+            .insert(name.to_string(), self.config.clone());
+        self.switch_profile(name);
+        Ok(())
+    }
 }
 
-fn run_analysis(
-    paths: Vec<PathBuf>,
+/// Analyze `paths` and report progress and the results, in list order, to
+/// `progress_tx`. With `workers` above 1 the files are spread over that many
+/// worker processes; a single file, or a pool that fails to start, runs on
+/// the Pdfium worker thread of this process.
+pub fn run_analysis(
+    paths: Vec<(PathBuf, Option<String>, Option<PageRange>)>,
     pdf_service: PdfiumService,
+    workers: usize,
+    config: Config,
+    use_cache: bool,
+    cancel: CancellationToken,
     progress_tx: Sender<AnalysisMessage>,
 ) {
-    let mut results = Vec::new();
     let total_files = paths.len();
+    let (events_tx, events_rx) = crossbeam_channel::unbounded();
 
-    for (file_idx, path) in paths.iter().enumerate() {
-        let filename = path
-            .file_name()
+    let filename = |path: &PathBuf| {
+        path.file_name()
             .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        // Send progress update
-        let _ = progress_tx.send(AnalysisMessage::Progress(AnalysisProgress {
-            current_file: filename.clone(),
-            current_analyzer: "Analyzing...".to_string(),
-            files_done: file_idx,
-            files_total: total_files,
-        }));
-
-        // Request analysis from the worker thread
-        match pdf_service.analyze_pdf(path.to_path_buf()) {
-            Ok(analysis) => {
-                results.push(PdfAnalysisResult {
-                    filename: analysis.filename,
-                    path: analysis.path,
-                    results: analysis.results,
-                    errors: analysis.errors,
-                });
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+
+    let jobs: Vec<AnalysisJob> = paths
+        .iter()
+        .map(|(path, password, page_range)| AnalysisJob {
+            path: path.clone(),
+            password: password.clone(),
+            page_range: *page_range,
+            config: config.clone(),
+            use_cache,
+            cancel: cancel.clone(),
+        })
+        .collect();
+
+    // Hand every file out up front; they are analyzed as workers become free
+    let pool = (workers > 1 && total_files > 1)
+        .then(|| PdfWorkerPool::spawn(workers.min(total_files)).ok())
+        .flatten();
+    match pool {
+        Some(pool) => pool.queue_batch(jobs, cancel.clone(), events_tx),
+        None => {
+            for job in jobs {
+                pdf_service.queue_analysis(job, events_tx.clone());
             }
-            Err(err) => {
-                let _ = progress_tx
-                    .send(AnalysisMessage::Error(format!(
-                        "Failed to analyze {}: {}",
-                        filename, err
-                    )))
-                    .unwrap();
-                continue;
+            drop(events_tx);
+        }
+    }
+
+    let _ = progress_tx.send(AnalysisMessage::Progress(AnalysisProgress {
+        current_file: paths
+            .first()
+            .map(|(path, _, _)| filename(path))
+            .unwrap_or_default(),
+        current_analyzer: "Analyzing...".to_string(),
+        files_done: 0,
+        files_total: total_files,
+        page_done: 0,
+        page_total: 0,
+    }));
+
+    // Progress counts the files finished so far, whichever worker finished them
+    let mut files_done = 0;
+    let mut finished = HashSet::new();
+    let mut analyses = HashMap::new();
+    for event in events_rx {
+        match event {
+            BatchEvent::Progress {
+                path,
+                analyzer,
+                page_done,
+                page_total,
+            } => {
+                let _ = progress_tx.send(AnalysisMessage::Progress(AnalysisProgress {
+                    current_file: filename(&path),
+                    current_analyzer: analyzer,
+                    files_done,
+                    files_total: total_files,
+                    page_done,
+                    page_total,
+                }));
+            }
+            BatchEvent::Done { path, result } => {
+                let name = filename(&path);
+                match result {
+                    Ok(analysis) => {
+                        analyses.insert(path.clone(), analysis);
+                    }
+                    // Cancelled before or part-way through this file; drop its incomplete results
+                    Err(AppError::Cancelled) => {}
+                    Err(err) => {
+                        let _ = progress_tx.send(AnalysisMessage::Error(format!(
+                            "Failed to analyze {}: {}",
+                            name, err
+                        )));
+                    }
+                }
+                finished.insert(path);
+                files_done += 1;
+
+                let _ = progress_tx.send(AnalysisMessage::Progress(AnalysisProgress {
+                    current_file: name,
+                    current_analyzer: "Done".to_string(),
+                    files_done,
+                    files_total: total_files,
+                    page_done: 0,
+                    page_total: 0,
+                }));
             }
         }
     }
 
-    let _ = progress_tx.send(AnalysisMessage::Complete(results));
+    // Left over when every worker process stopped before reaching them
+    if !cancel.is_cancelled() {
+        for (path, _, _) in paths.iter().filter(|(path, _, _)| !finished.contains(path)) {
+            let _ = progress_tx.send(AnalysisMessage::Error(format!(
+                "Failed to analyze {}: no analysis worker left",
+                filename(path)
+            )));
+        }
+    }
+
+    // Keep the results in the order of the PDF list
+    let results = paths
+        .iter()
+        .filter_map(|(path, _, page_range)| {
+            let mut result: PdfAnalysisResult = analyses.remove(path)?.into();
+            result.page_range = *page_range;
+            Some(result)
+        })
+        .collect();
+
+    let _ = progress_tx.send(if cancel.is_cancelled() {
+        AnalysisMessage::Cancelled(results)
+    } else {
+        AnalysisMessage::Complete(results)
+    });
+}
+
+/// Send every PDF below `directory`, in name order, descending at most
+/// `depth` levels.
+fn scan_folder(directory: &Path, depth: usize, tx: &Sender<FolderScanMessage>) {
+    let _ = tx.send(FolderScanMessage::Progress(directory.to_path_buf()));
+
+    let mut entries: Vec<PathBuf> = match std::fs::read_dir(directory) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+        Err(e) => {
+            let _ = tx.send(FolderScanMessage::Error(format!(
+                "Failed to read {}: {}",
+                directory.display(),
+                e
+            )));
+            return;
+        }
+    };
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            if depth > 0 {
+                scan_folder(&path, depth - 1, tx);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+        {
+            let _ = tx.send(FolderScanMessage::Found(path));
+        }
+    }
+}
+
+/// Match the rows and totals of an output's "before" and "after" version by
+/// filename and label. Files only in the snapshot are listed last.
+fn compare_output(before: Option<&OutputData>, after: &OutputData) -> ComparedOutput {
+    let before_rows = before.map_or(&[][..], |b| &b.per_pdf[..]);
+    let before_totals = before.map_or(&[][..], |b| &b.totals[..]);
+    let cells = |row: &OutputRow| row.values.iter().map(|(_, value)| value.clone()).collect();
+
+    let mut rows: Vec<ComparedRow> = after
+        .per_pdf
+        .iter()
+        .map(|row| ComparedRow {
+            filename: row.filename.clone(),
+            before: before_rows
+                .iter()
+                .find(|b| b.filename == row.filename)
+                .map(cells),
+            after: Some(cells(row)),
+        })
+        .collect();
+    rows.extend(
+        before_rows
+            .iter()
+            .filter(|b| !after.per_pdf.iter().any(|a| a.filename == b.filename))
+            .map(|row| ComparedRow {
+                filename: row.filename.clone(),
+                before: Some(cells(row)),
+                after: None,
+            }),
+    );
+
+    let totals = after
+        .totals
+        .iter()
+        .map(|(label, value)| {
+            let before = before_totals
+                .iter()
+                .find(|(l, _)| l == label)
+                .map(|(_, v)| v.clone());
+            (label.clone(), before, value.clone())
+        })
+        .collect();
+
+    ComparedOutput {
+        title: after.title.clone(),
+        columns: after.columns.clone(),
+        rows,
+        totals,
+    }
 }
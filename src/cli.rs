@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::mpsc;
+
+use clap::Parser;
+
+use crate::analyzer::CancellationToken;
+use crate::app::{run_analysis, AnalysisMessage};
+use crate::config::AppConfig;
+use crate::export::json;
+use crate::output::OutputRegistry;
+use crate::pdf::service::PdfiumService;
+use crate::watch::{FolderWatcher, LOG_FILE_NAME};
+
+/// Command line arguments. Without `--analyze` or `--watch` the GUI is started.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Analyze these PDFs without opening a window and print the result
+    #[arg(long, num_args = 1..)]
+    pub analyze: Vec<PathBuf>,
+
+    /// Id of the output module to print, e.g. "summary" or "cost"
+    #[arg(long, default_value = "summary")]
+    pub output: String,
+
+    /// Analysis saved with "Export JSON" for the "diff" output to compare against
+    #[arg(long)]
+    pub compare: Option<PathBuf>,
+
+    /// Analyze every new PDF dropped into this folder until interrupted,
+    /// appending the results to its CSV log
+    #[arg(long, conflicts_with = "analyze")]
+    pub watch: Option<PathBuf>,
+
+    /// Run as a worker process of a parallel analysis, talking JSON lines on
+    /// stdin and stdout
+    #[arg(long, hide = true)]
+    pub analysis_worker: bool,
+}
+
+/// Watch a folder headless, printing problems to stderr. Only returns if the
+/// watcher can't be started.
+pub fn watch(folder: PathBuf, pdf_service: PdfiumService) -> ExitCode {
+    let app_config = AppConfig::load();
+    let watcher = match FolderWatcher::start(
+        folder.clone(),
+        pdf_service,
+        app_config.active_config(),
+        app_config.use_analysis_cache,
+    ) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    eprintln!(
+        "Watching {}, logging to {}",
+        folder.display(),
+        folder.join(LOG_FILE_NAME).display()
+    );
+    for error in watcher.errors.iter() {
+        eprintln!("{}", error);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Run the analysis headless and print the chosen output's text to stdout.
+/// Fails if any file can't be loaded or analyzed.
+pub fn run(cli: Cli, pdf_service: PdfiumService) -> ExitCode {
+    let app_config = AppConfig::load();
+    let config = app_config.active_config();
+
+    let mut output_registry = OutputRegistry::default();
+    output_registry.apply_config(&config);
+    if let Some(ref path) = cli.compare {
+        match json::read_results_json(path) {
+            Ok(baseline) => output_registry.set_baseline(&baseline),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(output) = output_registry
+        .outputs()
+        .iter()
+        .find(|output| output.id() == cli.output)
+    else {
+        let ids: Vec<&str> = output_registry.outputs().iter().map(|o| o.id()).collect();
+        eprintln!(
+            "Unknown output '{}', available: {}",
+            cli.output,
+            ids.join(", ")
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let mut failed = false;
+    let mut paths = Vec::new();
+    for path in cli.analyze {
+        match pdf_service.load_pdf(path.clone(), None) {
+            Ok(_) => paths.push((path, None, None)),
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+        }
+    }
+
+    let (progress_tx, progress_rx) = mpsc::channel();
+    run_analysis(
+        paths,
+        pdf_service,
+        app_config.worker_count(),
+        config,
+        app_config.use_analysis_cache,
+        CancellationToken::new(),
+        progress_tx,
+    );
+
+    let mut results = Vec::new();
+    for message in progress_rx.try_iter() {
+        match message {
+            AnalysisMessage::Error(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+            AnalysisMessage::Complete(complete) | AnalysisMessage::Cancelled(complete) => {
+                results = complete
+            }
+            AnalysisMessage::Progress(_) => {}
+        }
+    }
+
+    for result in &results {
+        for error in &result.errors {
+            eprintln!("{}: {}", result.filename, error);
+        }
+    }
+
+    print!("{}", output.generate(&results).copyable_text);
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -1,17 +1,21 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::{AppError, Result};
+use crate::pdf::ThumbnailSize;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum ConfigValue {
     Bool(bool),
-    Float(f64),
+    // Int must come before Float: untagged deserialization would otherwise
+    // read whole numbers back as floats.
     Int(i64),
+    Float(f64),
     String(String),
+    List(Vec<String>),
 }
 
 impl ConfigValue {
@@ -28,6 +32,27 @@ impl ConfigValue {
             _ => None,
         }
     }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ConfigValue::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            ConfigValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            ConfigValue::List(v) => Some(v),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -36,26 +61,223 @@ pub struct ConfigParam {
     pub label: &'static str,
     pub default: ConfigValue,
     pub description: &'static str,
+    /// Lowest value the settings UI accepts, for numeric params.
+    pub min: Option<ConfigValue>,
+    /// Highest value the settings UI accepts, for numeric params.
+    pub max: Option<ConfigValue>,
+    /// How much the value changes per pixel dragged in the settings UI.
+    /// Defaults to 0.01 for floats and 1 for integers.
+    pub step: Option<f64>,
+    /// Fixed choices of a string param, shown as a dropdown.
+    pub options: Option<Vec<&'static str>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct Config {
+impl ConfigParam {
+    /// Whether `value` is one of the param's options; params without
+    /// options accept anything.
+    pub fn accepts(&self, value: &ConfigValue) -> bool {
+        match (&self.options, value) {
+            (Some(options), ConfigValue::String(v)) => options.contains(&v.as_str()),
+            (Some(_), _) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+impl Default for ConfigParam {
+    fn default() -> Self {
+        Self {
+            key: "",
+            label: "",
+            default: ConfigValue::Bool(false),
+            description: "",
+            min: None,
+            max: None,
+            step: None,
+            options: None,
+        }
+    }
+}
+
+/// `font_scale` of a new config
+pub const DEFAULT_FONT_SCALE: f64 = 1.4;
+
+/// Range of `font_scale` offered in the settings
+pub const FONT_SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.8..=2.5;
+
+/// Length of the "Recent Files" menu
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// Most worker processes used when `analysis_workers` is left at 0; each
+/// loads its own Pdfium and renders pages, so more mainly costs memory
+pub const MAX_DEFAULT_WORKERS: usize = 4;
+
+/// Profile used when the config file has none
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Everything persisted in `config.toml`: app-wide settings plus the named
+/// profiles holding analyzer and output settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppConfig {
+    /// PDFs loaded when the app was last closed, restored on the next start
     #[serde(default)]
-    pub analyzers: HashMap<String, HashMap<String, ConfigValue>>,
+    pub recent_paths: Vec<PathBuf>,
+    /// PDFs added to the list, newest first, for the "Recent Files" menu
     #[serde(default)]
-    pub outputs: HashMap<String, HashMap<String, ConfigValue>>,
+    pub recent_files: Vec<PathBuf>,
+    /// Where the "Add PDFs" and "Add Folder" dialogs open
+    #[serde(default)]
+    pub last_directory: Option<PathBuf>,
+    /// Analyze new PDFs appearing in `watch_folder` while the app runs
+    #[serde(default)]
+    pub watch_enabled: bool,
+    #[serde(default)]
+    pub watch_folder: Option<PathBuf>,
+    /// How many directory levels "Add Folder" descends, guarding against symlink loops
+    #[serde(default = "default_max_folder_depth")]
+    pub max_folder_depth: usize,
+    #[serde(default = "default_thumbnail_width")]
+    pub thumbnail_width: usize,
+    #[serde(default = "default_thumbnail_max_height")]
+    pub thumbnail_max_height: usize,
+    /// Reuse stored results for unchanged files; off forces a fresh analysis
+    #[serde(default = "default_use_analysis_cache")]
+    pub use_analysis_cache: bool,
+    /// Worker processes analyzing a batch in parallel; 0 uses one per CPU
+    /// core, up to `MAX_DEFAULT_WORKERS`
+    #[serde(default)]
+    pub analysis_workers: usize,
+    /// Show how long each analyzer took on average in the Results tab
+    #[serde(default)]
+    pub show_timing: bool,
+    /// Size of all text relative to egui's default
+    #[serde(default = "default_font_scale")]
+    pub font_scale: f64,
+    /// "system", "light" or "dark"; anything else follows the system
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// Window position and size in points when the app was last closed,
+    /// restored on the next start. No position lets the system place it.
+    #[serde(default)]
+    pub window_x: Option<i32>,
+    #[serde(default)]
+    pub window_y: Option<i32>,
+    #[serde(default = "default_window_width")]
+    pub window_width: f32,
+    #[serde(default = "default_window_height")]
+    pub window_height: f32,
+    /// Size and position above are those from before maximizing
+    #[serde(default)]
+    pub window_maximized: bool,
+    #[serde(default = "default_profile_name")]
+    pub active_profile: String,
+    #[serde(default)]
+    pub profiles: HashMap<String, Config>,
 }
 
-impl Config {
+fn default_profile_name() -> String {
+    DEFAULT_PROFILE.to_string()
+}
+
+fn default_max_folder_depth() -> usize {
+    5
+}
+
+fn default_font_scale() -> f64 {
+    DEFAULT_FONT_SCALE
+}
+
+fn default_theme() -> String {
+    "system".to_string()
+}
+
+fn default_window_width() -> f32 {
+    900.0
+}
+
+fn default_window_height() -> f32 {
+    700.0
+}
+
+fn default_thumbnail_width() -> usize {
+    ThumbnailSize::default().width
+}
+
+fn default_thumbnail_max_height() -> usize {
+    ThumbnailSize::default().max_height
+}
+
+fn default_use_analysis_cache() -> bool {
+    true
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            recent_paths: Vec::new(),
+            recent_files: Vec::new(),
+            last_directory: None,
+            watch_enabled: false,
+            watch_folder: None,
+            max_folder_depth: default_max_folder_depth(),
+            thumbnail_width: default_thumbnail_width(),
+            thumbnail_max_height: default_thumbnail_max_height(),
+            use_analysis_cache: default_use_analysis_cache(),
+            analysis_workers: 0,
+            show_timing: false,
+            font_scale: default_font_scale(),
+            theme: default_theme(),
+            window_x: None,
+            window_y: None,
+            window_width: default_window_width(),
+            window_height: default_window_height(),
+            window_maximized: false,
+            active_profile: default_profile_name(),
+            profiles: HashMap::from([(default_profile_name(), Config::default())]),
+        }
+    }
+}
+
+impl AppConfig {
     pub fn config_path() -> Option<PathBuf> {
         dirs::config_dir().map(|p| p.join("pdf_analyzer").join("config.toml"))
     }
 
+    /// Directory of the cached analysis results.
+    pub fn cache_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("pdf_analyzer").join("cache"))
+    }
+
+    /// How many files a batch analyzes at once, resolving 0 to the CPU
+    /// count capped at `MAX_DEFAULT_WORKERS`.
+    pub fn worker_count(&self) -> usize {
+        match self.analysis_workers {
+            0 => std::thread::available_parallelism()
+                .map_or(1, |n| n.get())
+                .min(MAX_DEFAULT_WORKERS),
+            count => count,
+        }
+    }
+
     pub fn load() -> Self {
-        Self::config_path()
-            .and_then(|path| fs::read_to_string(&path).ok())
-            .and_then(|content| toml::from_str(&content).ok())
-            .unwrap_or_default()
+        let Some(content) = Self::config_path().and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return Self::default();
+        };
+        let Ok(mut app_config) = toml::from_str::<AppConfig>(&content) else {
+            return Self::default();
+        };
+
+        // Files written before profiles existed keep their settings at the top level
+        if app_config.profiles.is_empty() {
+            let config = toml::from_str::<Config>(&content).unwrap_or_default();
+            app_config.profiles.insert(default_profile_name(), config);
+            app_config.active_profile = default_profile_name();
+        }
+        if !app_config.profiles.contains_key(&app_config.active_profile) {
+            app_config.active_profile = app_config.profile_names()[0].clone();
+        }
+        app_config
     }
 
     pub fn save(&self) -> Result<()> {
@@ -73,6 +295,108 @@ impl Config {
         Ok(())
     }
 
+    /// Move `path` to the front of the recent files, dropping the oldest
+    /// beyond `MAX_RECENT_FILES`.
+    pub fn add_recent_file(&mut self, path: PathBuf) {
+        self.recent_files.retain(|recent| *recent != path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    pub fn thumbnail_size(&self) -> ThumbnailSize {
+        ThumbnailSize {
+            width: self.thumbnail_width,
+            max_height: self.thumbnail_max_height,
+        }
+    }
+
+    /// Profile names in alphabetical order.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Settings of the active profile.
+    pub fn active_config(&self) -> Config {
+        self.profiles
+            .get(&self.active_profile)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Analyzer and output settings of one profile
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Config {
+    /// Analyzers and outputs switched on or off by id; missing ones are enabled
+    #[serde(default)]
+    pub enabled: HashMap<String, bool>,
+    #[serde(default)]
+    pub analyzers: HashMap<String, HashMap<String, ConfigValue>>,
+    #[serde(default)]
+    pub outputs: HashMap<String, HashMap<String, ConfigValue>>,
+}
+
+impl Config {
+    /// Write these settings to a standalone TOML file for sharing.
+    pub fn export_to(&self, path: &Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).map_err(|e| AppError::ConfigError(e.to_string()))?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Read settings written by `export_to`.
+    pub fn import_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            AppError::ConfigError(format!(
+                "'{}' is not a valid settings file: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Remove values that aren't among their param's options, so the default
+    /// applies instead. Returns a message for every removed value.
+    pub fn reject_invalid_options(
+        &mut self,
+        analyzer_params: &[(&str, &str, Vec<ConfigParam>)],
+        output_params: &[(&str, &str, Vec<ConfigParam>)],
+    ) -> Vec<String> {
+        let mut rejected = Vec::new();
+        for (values, params) in [
+            (&mut self.analyzers, analyzer_params),
+            (&mut self.outputs, output_params),
+        ] {
+            for (id, name, params) in params {
+                let Some(values) = values.get_mut(*id) else {
+                    continue;
+                };
+                for param in params {
+                    if let Some(value) = values.get(param.key).filter(|v| !param.accepts(v)) {
+                        rejected.push(format!(
+                            "{}: {:?} is not a valid {}, using the default",
+                            name, value, param.label
+                        ));
+                        values.remove(param.key);
+                    }
+                }
+            }
+        }
+        rejected
+    }
+
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.enabled.get(id).copied().unwrap_or(true)
+    }
+
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        self.enabled.insert(id.to_string(), enabled);
+    }
+
     pub fn get_analyzer_value(&self, analyzer_id: &str, key: &str) -> Option<&ConfigValue> {
         self.analyzers.get(analyzer_id)?.get(key)
     }
@@ -95,3 +419,93 @@ impl Config {
             .insert(key.to_string(), value);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_variants() -> Vec<ConfigValue> {
+        vec![
+            ConfigValue::Bool(true),
+            ConfigValue::Int(3),
+            ConfigValue::Float(0.5),
+            ConfigValue::String("a4".to_string()),
+            ConfigValue::List(vec!["a".to_string(), "b".to_string()]),
+        ]
+    }
+
+    #[test]
+    fn accessors_match_only_their_own_variant() {
+        // all_variants() lists the variants in the order of the accessors below
+        for (variant, value) in all_variants().iter().enumerate() {
+            let matched = [
+                value.as_bool().is_some(),
+                value.as_int().is_some(),
+                value.as_float().is_some(),
+                value.as_string().is_some(),
+                value.as_list().is_some(),
+            ];
+            for (accessor, matched) in matched.into_iter().enumerate() {
+                assert_eq!(matched, accessor == variant, "{value:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn accessors_return_the_value() {
+        assert_eq!(ConfigValue::Bool(true).as_bool(), Some(true));
+        assert_eq!(ConfigValue::Int(3).as_int(), Some(3));
+        assert_eq!(ConfigValue::Float(0.5).as_float(), Some(0.5));
+        assert_eq!(
+            ConfigValue::String("a4".to_string()).as_string(),
+            Some("a4")
+        );
+        assert_eq!(
+            ConfigValue::List(vec!["a".to_string()]).as_list(),
+            Some(&["a".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn whole_numbers_deserialize_as_int() {
+        assert_eq!(
+            serde_json::from_str::<ConfigValue>("3").unwrap(),
+            ConfigValue::Int(3)
+        );
+        assert_eq!(
+            serde_json::from_str::<ConfigValue>("0.5").unwrap(),
+            ConfigValue::Float(0.5)
+        );
+        assert_eq!(
+            serde_json::from_str::<ConfigValue>("3.0").unwrap(),
+            ConfigValue::Float(3.0)
+        );
+    }
+
+    #[test]
+    fn config_values_round_trip_through_toml() {
+        let mut config = Config::default();
+        for (i, value) in all_variants().into_iter().enumerate() {
+            config.set_analyzer_value("test", &format!("key{i}"), value);
+        }
+
+        let content = toml::to_string_pretty(&config).unwrap();
+        let loaded: Config = toml::from_str(&content).unwrap();
+
+        for (i, value) in all_variants().into_iter().enumerate() {
+            assert_eq!(
+                loaded.get_analyzer_value("test", &format!("key{i}")),
+                Some(&value)
+            );
+        }
+    }
+
+    #[test]
+    fn worker_count_caps_only_the_default() {
+        let mut app_config = AppConfig::default();
+        assert!((1..=MAX_DEFAULT_WORKERS).contains(&app_config.worker_count()));
+
+        app_config.analysis_workers = MAX_DEFAULT_WORKERS + 2;
+        assert_eq!(app_config.worker_count(), MAX_DEFAULT_WORKERS + 2);
+    }
+}
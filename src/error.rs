@@ -8,6 +8,18 @@ pub enum AppError {
     #[error("Failed to load PDF '{path}': {reason}")]
     PdfLoad { path: String, reason: String },
 
+    #[error("PDF '{path}' is encrypted and requires a password")]
+    PasswordRequired { path: String },
+
+    #[error("Skipped '{path}': same content as '{existing}' already in the list")]
+    DuplicatePdf { path: String, existing: String },
+
+    #[error("Skipped '{path}': already in the list")]
+    AlreadyListed { path: String },
+
+    #[error("Unsupported PDF version '{version}' in '{path}'")]
+    UnsupportedPdfVersion { version: String, path: String },
+
     #[error("Failed to render page {page}: {reason}")]
     RenderError { page: usize, reason: String },
 
@@ -21,6 +33,19 @@ pub enum AppError {
         reason: String,
     },
 
+    #[error("Export error: {0}")]
+    ExportError(String),
+
+    #[error("Folder watch error: {0}")]
+    WatchError(String),
+
+    /// Failure reported by an analysis worker process, already formatted
+    #[error("{0}")]
+    AnalysisWorker(String),
+
+    #[error("Analysis cancelled")]
+    Cancelled,
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::Path;
+
+use super::AnalysisRun;
+use crate::error::Result;
+use crate::output::OutputData;
+
+/// Write an output's table as RFC 4180 CSV: the column header, one row per
+/// PDF and, after a blank line, the totals and the analysis time as
+/// label/value pairs.
+pub fn write_output_csv(output: &OutputData, run: Option<&AnalysisRun>, path: &Path) -> Result<()> {
+    fs::write(path, output_csv(output, run))?;
+    Ok(())
+}
+
+fn output_csv(output: &OutputData, run: Option<&AnalysisRun>) -> String {
+    let mut lines = vec![record(&output.columns)];
+
+    for row in &output.per_pdf {
+        let values = row.values.iter().map(|(_, value)| value);
+        lines.push(record(std::iter::once(&row.filename).chain(values)));
+    }
+
+    if !output.totals.is_empty() || run.is_some() {
+        lines.push(String::new());
+        for (label, value) in &output.totals {
+            lines.push(record([label, value]));
+        }
+    }
+    if let Some(run) = run {
+        lines.push(record(["Analyzed At", &run.analyzed_at]));
+        lines.push(record([
+            "Duration (s)",
+            &format!("{:.1}", run.duration_seconds),
+        ]));
+        if run.cancelled {
+            lines.push(record(["Cancelled", "Yes"]));
+        }
+    }
+
+    let mut csv = lines.join("\r\n");
+    csv.push_str("\r\n");
+    csv
+}
+
+/// Join fields into one CSV record, without the line break.
+pub fn record(fields: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    fields
+        .into_iter()
+        .map(|f| field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+pub fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
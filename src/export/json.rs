@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::AnalysisRun;
+use crate::analyzer::PdfAnalysisResult;
+use crate::error::{AppError, Result};
+
+/// Layout of an export: the run's timestamp and duration next to the results
+#[derive(Serialize, Deserialize)]
+struct ResultsExport<'a> {
+    #[serde(flatten)]
+    run: Option<AnalysisRun>,
+    results: Cow<'a, [PdfAnalysisResult]>,
+}
+
+/// Write all analysis results as JSON, one object per PDF in `results`, with
+/// when and how long they were analyzed if known.
+pub fn write_results_json(
+    results: &[PdfAnalysisResult],
+    run: Option<&AnalysisRun>,
+    path: &Path,
+) -> Result<()> {
+    let export = ResultsExport {
+        run: run.cloned(),
+        results: Cow::Borrowed(results),
+    };
+    let json =
+        serde_json::to_string_pretty(&export).map_err(|e| AppError::ExportError(e.to_string()))?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Read results written by `write_results_json`, e.g. to compare with a new analysis.
+pub fn read_results_json(path: &Path) -> Result<Vec<PdfAnalysisResult>> {
+    let json = fs::read_to_string(path)?;
+    // Exports before the run was recorded were a bare array of results
+    let results = if json.trim_start().starts_with('[') {
+        serde_json::from_str(&json)
+    } else {
+        serde_json::from_str::<ResultsExport>(&json).map(|export| export.results.into_owned())
+    };
+    results.map_err(|e| {
+        AppError::ExportError(format!(
+            "'{}' is not an analysis export: {}",
+            path.display(),
+            e
+        ))
+    })
+}
@@ -0,0 +1,15 @@
+pub mod csv;
+pub mod json;
+
+use serde::{Deserialize, Serialize};
+
+/// When the exported results were analyzed and how long the analysis took
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisRun {
+    /// Local time the analysis finished, RFC 3339
+    pub analyzed_at: String,
+    pub duration_seconds: f64,
+    /// The analysis was cancelled, so only the files finished before are included
+    #[serde(default)]
+    pub cancelled: bool,
+}
@@ -1,64 +1,210 @@
 mod analyzer;
 mod app;
+mod cli;
 mod config;
 mod error;
+mod export;
 mod output;
 mod pdf;
+mod watch;
+
+use std::process::ExitCode;
 
 use app::App;
+use clap::Parser;
 use eframe::egui;
+use output::OutputModule;
+
+fn main() -> ExitCode {
+    let cli = cli::Cli::parse();
 
-fn main() -> eframe::Result<()> {
     // start pdfium worker
-    crate::pdf::service::PdfiumWorker::spawn()
-        .map_err(|err| eframe::Error::AppCreation(Box::new(err)))?;
+    let pdfium_error = crate::pdf::service::PdfiumWorker::spawn().err();
+
+    if cli.analysis_worker {
+        crate::pdf::worker::serve(pdfium_error);
+        return ExitCode::SUCCESS;
+    }
+
+    if !cli.analyze.is_empty() || cli.watch.is_some() {
+        if let Some(err) = pdfium_error {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+        return match crate::pdf::service::PdfiumWorker::service() {
+            Ok(pdf_service) => match cli.watch.clone() {
+                Some(folder) => cli::watch(folder, pdf_service),
+                None => cli::run(cli, pdf_service),
+            },
+            Err(err) => {
+                eprintln!("{}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
 
+    match run_gui(pdfium_error) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Without Pdfium the app can't do anything, so `pdfium_error` replaces the
+/// main window with instructions for installing it.
+fn run_gui(pdfium_error: Option<error::AppError>) -> eframe::Result<()> {
+    let app_config = config::AppConfig::load();
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size([app_config.window_width, app_config.window_height])
+        .with_min_inner_size([600.0, 400.0])
+        .with_maximized(app_config.window_maximized)
+        .with_drag_and_drop(true);
+    if let (Some(x), Some(y)) = (app_config.window_x, app_config.window_y) {
+        viewport = viewport.with_position([x as f32, y as f32]);
+    }
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([900.0, 700.0])
-            .with_min_inner_size([600.0, 400.0])
-            .with_drag_and_drop(true),
+        viewport,
         ..Default::default()
     };
 
     eframe::run_native(
         "PDF Analyzer",
         options,
-        Box::new(|cc| {
-            // Set up larger fonts
-            let mut style = (*cc.egui_ctx.style()).clone();
-
-            // Increase all font sizes by ~40%
-            for (_text_style, font_id) in style.text_styles.iter_mut() {
-                font_id.size *= 1.4;
-            }
-
-            cc.egui_ctx.set_style(style);
+        Box::new(move |cc| {
+            cc.egui_ctx.set_theme(theme_preference(&app_config.theme));
+            apply_font_scale(&cc.egui_ctx, app_config.font_scale);
 
-            Ok(Box::new(App::default()))
+            let app: Box<dyn eframe::App> = match pdfium_error {
+                Some(err) => Box::new(PdfiumMissing {
+                    reason: err.to_string(),
+                }),
+                None => Box::new(App::default()),
+            };
+            Ok(app)
         }),
     )
 }
 
+/// Scale all font sizes from egui's defaults, in the light and dark style.
+fn apply_font_scale(ctx: &egui::Context, scale: f64) {
+    // Hand-edited config files may hold anything
+    let (min, max) = config::FONT_SCALE_RANGE.into_inner();
+    let scale = scale.clamp(min, max);
+    ctx.all_styles_mut(|style| {
+        style.text_styles = egui::style::default_text_styles();
+        for (_text_style, font_id) in style.text_styles.iter_mut() {
+            font_id.size *= scale as f32;
+        }
+    });
+}
+
+/// Theme of a `theme` config value, following the system for unknown values.
+fn theme_preference(theme: &str) -> egui::ThemePreference {
+    match theme {
+        "light" => egui::ThemePreference::Light,
+        "dark" => egui::ThemePreference::Dark,
+        _ => egui::ThemePreference::System,
+    }
+}
+
+/// Shown instead of the app when the Pdfium library could not be loaded.
+struct PdfiumMissing {
+    reason: String,
+}
+
+impl eframe::App for PdfiumMissing {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let library = pdfium_render::prelude::Pdfium::pdfium_platform_library_name();
+        let library = library.to_string_lossy();
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.heading("⚠ Pdfium library not found");
+                ui.add_space(12.0);
+                ui.label("PDF Analyzer uses Pdfium to open and render PDFs and can't run without it");
+            });
+            ui.add_space(20.0);
+
+            ui.label("To install it:");
+            ui.label(
+                "1. Download a Pdfium build for your platform, e.g. from \
+                 github.com/ArtifexSoftware/pdfium-binaries",
+            );
+            ui.label(format!(
+                "2. Copy {} into the directory PDF Analyzer is started from, or install it system-wide",
+                library
+            ));
+            ui.label("3. Restart PDF Analyzer");
+            ui.add_space(20.0);
+
+            ui.collapsing("Details", |ui| {
+                ui.monospace(&self.reason);
+            });
+            ui.add_space(12.0);
+            if ui.button("Quit").clicked() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        });
+    }
+}
+
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Handle dropped files
-        ctx.input(|i| {
-            for file in &i.raw.dropped_files {
-                if let Some(path) = &file.path {
-                    if path.extension().map(|e| e == "pdf").unwrap_or(false) {
-                        if let Err(e) = self.add_pdf(path.clone()) {
-                            self.errors.push(e.to_string());
-                        }
-                    }
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+        for file in dropped_files {
+            let Some(path) = file.path else {
+                continue;
+            };
+
+            let is_pdf = path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("pdf"));
+            if is_pdf {
+                if let Err(e) = self.drop_pdf(path) {
+                    self.errors.push(e.to_string());
                 }
+            } else {
+                self.errors.push(format!(
+                    "Skipped {}: only PDF files can be added",
+                    path.file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string())
+                ));
             }
-        });
+        }
 
         self.update_analysis();
+        self.update_pending_loads();
+        self.update_unlocks();
+        self.update_reanalysis();
+        self.update_thumbnails();
+        self.update_validation();
+        self.update_preview();
+        self.update_file_detail();
+        self.update_folder_scan();
+        self.update_watch_errors();
+        self.handle_shortcuts(ctx);
+        self.remember_window_geometry(ctx);
+        self.save_session_if_changed();
 
-        if matches!(self.state, app::AppState::Analyzing) {
+        if matches!(self.state, app::AppState::Analyzing)
+            || self.folder_scan.is_some()
+            || self.is_loading_pdfs()
+            || self.is_unlocking()
+            || self.is_reanalyzing()
+            || self.has_pending_thumbnails()
+            || self.is_validating()
+            || self.is_preview_loading()
+            || self.is_file_detail_loading()
+        {
             ctx.request_repaint();
+        } else if self.watcher.is_some() {
+            // Pick up folder watch errors while the window is idle
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
         }
 
         // Settings window
@@ -66,6 +212,21 @@ impl eframe::App for App {
             self.show_settings_window(ctx);
         }
 
+        // Name dialog for a new profile
+        if self.new_profile_name.is_some() {
+            self.show_new_profile_window(ctx);
+        }
+
+        // Page preview of a PDF
+        if self.preview.is_some() {
+            self.show_preview_window(ctx);
+        }
+
+        // Password prompt for encrypted PDFs
+        if !self.password_prompts.is_empty() {
+            self.show_password_window(ctx);
+        }
+
         // Top panel with title and settings button
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -74,6 +235,13 @@ impl eframe::App for App {
                     if ui.button("⚙ Settings").clicked() {
                         self.show_settings = true;
                     }
+
+                    if ui.button("➕ New Profile").clicked() {
+                        self.new_profile_name = Some(String::new());
+                    }
+
+                    self.render_profile_selector(ui, "profile");
+                    ui.label("Profile:");
                 });
             });
 
@@ -83,6 +251,9 @@ impl eframe::App for App {
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.current_tab, app::AppTab::PdfList, "PDF List");
                 ui.selectable_value(&mut self.current_tab, app::AppTab::Results, "Results");
+                if self.comparison_results.is_some() {
+                    ui.selectable_value(&mut self.current_tab, app::AppTab::Compare, "Compare");
+                }
             });
         });
 
@@ -102,18 +273,72 @@ impl eframe::App for App {
                 ui.add_space(4.0);
             }
 
+            // PDFs dropped during analysis
+            if !self.queued_pdfs.is_empty() {
+                ui.label(format!(
+                    "{} files queued — they are added when the analysis finishes",
+                    self.queued_pdfs.len()
+                ));
+                ui.add_space(4.0);
+            } else if self.queued_pdfs_added {
+                ui.label("Files queued — re-run analysis to include them");
+                ui.add_space(4.0);
+            }
+
+            // Folder scan status
+            if let Some(ref scan) = self.folder_scan {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!(
+                        "Scanning {} ({} PDFs found)",
+                        scan.directory.display(),
+                        scan.found
+                    ));
+                });
+                ui.add_space(4.0);
+            }
+
             // Progress bar during analysis
+            let mut cancel_clicked = false;
             if let Some(ref progress) = self.progress {
                 if matches!(self.state, app::AppState::Analyzing) {
                     let fraction = progress.files_done as f32 / progress.files_total as f32;
                     ui.add(egui::ProgressBar::new(fraction).show_percentage());
-                    ui.label(format!(
-                        "Analyzing: {} - {}",
-                        progress.current_file, progress.current_analyzer
-                    ));
+                    ui.horizontal(|ui| {
+                        if self.is_cancelling() {
+                            ui.label("Cancelling...");
+                        } else {
+                            let mut status = format!(
+                                "Analyzing: {} - {}",
+                                progress.current_file, progress.current_analyzer
+                            );
+                            if progress.page_total > 0 {
+                                status.push_str(&format!(
+                                    ": page {}/{}",
+                                    progress.page_done, progress.page_total
+                                ));
+                            }
+                            ui.label(status);
+                            if ui.button("⏹ Cancel").clicked() {
+                                cancel_clicked = true;
+                            }
+                        }
+                    });
                 }
             }
 
+            if cancel_clicked {
+                self.cancel_analysis();
+            }
+
+            // Status line
+            let total_pages: usize = self.pdfs.iter().map(|pdf| pdf.file.page_count).sum();
+            let mut status = format!("{} files · {} pages total", self.pdfs.len(), total_pages);
+            if let Some(last_run) = self.last_run {
+                status.push_str(&format!(" · Last run: {}", last_run.format("%H:%M:%S")));
+            }
+            ui.weak(status);
+
             ui.add_space(4.0);
         });
 
@@ -121,39 +346,228 @@ impl eframe::App for App {
         egui::CentralPanel::default().show(ctx, |ui| match self.current_tab {
             app::AppTab::PdfList => self.show_pdf_list_tab(ui, ctx),
             app::AppTab::Results => self.show_results_tab(ui, ctx),
+            app::AppTab::FileDetail(index) => self.show_file_detail_tab(ui, ctx, index),
+            app::AppTab::Compare => self.show_compare_tab(ui),
         });
+
+        self.show_toasts(ctx);
+
+        // Drop target hint while files are dragged over the window
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("drop_hint"),
+            ));
+            let screen_rect = ctx.screen_rect();
+            painter.rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(180));
+            painter.text(
+                screen_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop PDF files to add them",
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_session();
     }
 }
 
+/// Keys and what they do, listed in the settings window
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl+O", "Add PDFs"),
+    ("Ctrl+Enter", "Analyze"),
+    ("Delete", "Remove the clicked file from the PDF list"),
+    ("Ctrl+E", "Export the results as JSON"),
+    ("Ctrl+,", "Open the settings"),
+    ("Escape", "Close the settings"),
+];
+
 impl App {
+    /// Handle the keys listed in `SHORTCUTS`.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        use egui::{Key, KeyboardShortcut, Modifiers};
+
+        let open = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+        let analyze = KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter);
+        let export = KeyboardShortcut::new(Modifiers::COMMAND, Key::E);
+        let settings = KeyboardShortcut::new(Modifiers::COMMAND, Key::Comma);
+
+        if ctx.input_mut(|i| i.consume_shortcut(&open)) {
+            self.open_add_pdfs_dialog();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&analyze)) && self.can_analyze() {
+            self.start_analysis();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&export)) && !self.analysis_results.is_empty() {
+            self.export_results_json();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&settings)) {
+            self.show_settings = true;
+        }
+
+        // Not while typing, e.g. in the results filter or a password field
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if self.current_tab == app::AppTab::PdfList
+            && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Delete))
+        {
+            if let Some(index) = self.current_pdf {
+                self.remove_pdf(index);
+            }
+        }
+        if self.show_settings && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.show_settings = false;
+        }
+    }
+
+    /// Track the window's position and size, saved with the session on exit.
+    /// Kept from before maximizing so un-maximizing after a restart restores it.
+    fn remember_window_geometry(&mut self, ctx: &egui::Context) {
+        let (maximized, outer, inner) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.maximized.unwrap_or(false),
+                viewport.outer_rect,
+                viewport.inner_rect,
+            )
+        });
+        self.app_config.window_maximized = maximized;
+        if maximized {
+            return;
+        }
+        // Not reported on some platforms, e.g. the position on Wayland
+        if let Some(outer) = outer {
+            self.app_config.window_x = Some(outer.min.x.round() as i32);
+            self.app_config.window_y = Some(outer.min.y.round() as i32);
+        }
+        if let Some(inner) = inner {
+            self.app_config.window_width = inner.width();
+            self.app_config.window_height = inner.height();
+        }
+    }
+
+    fn open_add_pdfs_dialog(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("PDF files", &["pdf"])
+            .set_title("Select PDF files");
+        if let Some(ref dir) = self.app_config.last_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(paths) = dialog.pick_files() {
+            self.remember_directory(paths.first());
+            for path in paths {
+                if let Err(e) = self.add_pdf(path) {
+                    self.errors.push(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// Files still being opened would be left out of the analysis
+    fn can_analyze(&self) -> bool {
+        !self.pdfs.is_empty()
+            && !self.is_loading_pdfs()
+            && matches!(self.state, app::AppState::Ready | app::AppState::Results)
+    }
+
     fn show_pdf_list_tab(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.horizontal(|ui| {
-            if ui.button("+ Add PDFs").clicked() {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("PDF files", &["pdf"])
-                    .set_title("Select PDF files")
-                    .pick_files()
-                {
-                    for path in paths {
-                        if let Err(e) = self.add_pdf(path) {
-                            self.errors.push(e.to_string());
-                        }
-                    }
+            if ui.button("+ Add PDFs").on_hover_text("Ctrl+O").clicked() {
+                self.open_add_pdfs_dialog();
+            }
+
+            if ui.button("📂 Add Folder").clicked() {
+                let mut dialog = rfd::FileDialog::new().set_title("Select a folder with PDF files");
+                if let Some(ref dir) = self.app_config.last_directory {
+                    dialog = dialog.set_directory(dir);
+                }
+
+                if let Some(folder) = dialog.pick_folder() {
+                    self.remember_directory(Some(&folder));
+                    self.add_folder(folder);
                 }
             }
 
+            ui.menu_button("🕘 Recent Files", |ui| {
+                if self.app_config.recent_files.is_empty() {
+                    ui.weak("No recent files");
+                    return;
+                }
+
+                let mut to_open = None;
+                for path in &self.app_config.recent_files {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string());
+                    let exists = path.exists();
+                    let text = if exists {
+                        egui::RichText::new(name)
+                    } else {
+                        egui::RichText::new(format!("{} (missing)", name)).weak()
+                    };
+                    if ui
+                        .add_enabled(exists, egui::Button::new(text))
+                        .on_hover_text(path.display().to_string())
+                        .clicked()
+                    {
+                        to_open = Some(path.clone());
+                    }
+                }
+                ui.separator();
+                if ui.button("Clear Recent").clicked() {
+                    self.app_config.recent_files.clear();
+                    ui.close_menu();
+                }
+
+                if let Some(path) = to_open {
+                    if let Err(e) = self.add_pdf(path) {
+                        self.errors.push(e.to_string());
+                    }
+                    ui.close_menu();
+                }
+            });
+
             ui.add_space(16.0);
 
-            let can_analyze = !self.pdfs.is_empty()
-                && matches!(self.state, app::AppState::Ready | app::AppState::Results);
+            let can_analyze = self.can_analyze();
+            let analyze_label = if self.selected.is_empty() {
+                "▶ Analyze".to_string()
+            } else {
+                format!("▶ Analyze Selected ({})", self.selected.len())
+            };
             ui.add_enabled_ui(can_analyze, |ui| {
-                if ui.button("▶ Analyze").clicked() {
+                if ui
+                    .button(analyze_label)
+                    .on_hover_text("Ctrl+Enter")
+                    .clicked()
+                {
                     self.start_analysis();
                 }
             });
 
             if !self.pdfs.is_empty() {
                 ui.add_space(16.0);
+                let select_label = if self.selected.len() == self.pdfs.len() {
+                    "Deselect All"
+                } else {
+                    "Select All"
+                };
+                if ui.button(select_label).clicked() {
+                    self.toggle_select_all();
+                }
+                if ui
+                    .add_enabled(!self.is_validating(), egui::Button::new("✓ Validate All"))
+                    .on_hover_text("Check that every file still opens, without analyzing it")
+                    .clicked()
+                {
+                    self.validate_all();
+                }
                 if ui.button("Clear All").clicked() {
                     self.clear();
                 }
@@ -164,27 +578,59 @@ impl App {
         ui.separator();
         ui.add_space(8.0);
 
-        if self.pdfs.is_empty() {
+        if self.is_loading_pdfs() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("Opening {} file(s)...", self.pending_loads.len()));
+            });
+            ui.add_space(8.0);
+        }
+
+        if self.pdfs.is_empty() && !self.is_loading_pdfs() {
             ui.vertical_centered(|ui| {
                 ui.add_space(50.0);
                 ui.label(egui::RichText::new("No PDF files added").size(16.0).weak());
                 ui.label("Click '+ Add PDFs' or drag and drop files here");
             });
         } else {
+            // Paths of analyzed PDFs that contain JavaScript
+            let javascript_paths: Vec<String> = self
+                .analysis_results
+                .iter()
+                .filter(|result| {
+                    result.results.iter().any(|r| {
+                        matches!(
+                            r,
+                            analyzer::AnalysisResult::JavaScriptAnalysis {
+                                has_javascript: true,
+                                ..
+                            }
+                        )
+                    })
+                })
+                .map(|result| result.path.clone())
+                .collect();
+
             egui::ScrollArea::vertical().show(ui, |ui| {
                 let mut to_remove = None;
+                let mut to_move = None;
+                let mut to_reanalyze = None;
+                let mut thumbnails_to_request = Vec::new();
+                let mut to_preview = None;
+                let mut to_toggle = None;
+                let mut to_focus = None;
+                let pdf_count = self.pdfs.len();
+                let is_analyzing = matches!(self.state, app::AppState::Analyzing);
+                let reanalyzing: Vec<std::path::PathBuf> =
+                    self.reanalyzing.keys().cloned().collect();
 
                 for (idx, loaded_pdf) in self.pdfs.iter_mut().enumerate() {
                     // Lazily create texture from thumbnail if needed
                     if loaded_pdf.texture.is_none() {
                         if let Some(ref thumbnail) = loaded_pdf.file.thumbnail {
-                            let size = [thumbnail.width() as usize, thumbnail.height() as usize];
-                            let pixels = thumbnail.as_flat_samples();
-                            let color_image =
-                                egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
                             let texture = ctx.load_texture(
                                 format!("pdf_thumb_{}", idx),
-                                color_image,
+                                color_image(thumbnail),
                                 egui::TextureOptions::LINEAR,
                             );
                             loaded_pdf.texture = Some(texture);
@@ -192,39 +638,190 @@ impl App {
                     }
 
                     ui.horizontal(|ui| {
+                        let mut checked = self.selected.contains(&idx);
+                        if ui
+                            .checkbox(&mut checked, "")
+                            .on_hover_text("Analyze only the checked files")
+                            .changed()
+                        {
+                            to_toggle = Some(idx);
+                        }
+
                         // Display thumbnail if available
                         if let Some(ref texture) = loaded_pdf.texture {
                             let size = texture.size_vec2();
                             let scaled_height = 60.0;
                             let scale = scaled_height / size.y;
                             let scaled_size = egui::vec2(size.x * scale, scaled_height);
-                            ui.image((texture.id(), scaled_size));
+                            if ui
+                                .add(
+                                    egui::Image::new((texture.id(), scaled_size))
+                                        .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("Click to preview all pages")
+                                .clicked()
+                            {
+                                to_preview = Some(idx);
+                            }
+                            ui.add_space(8.0);
+                        } else if loaded_pdf.thumbnail_status != app::ThumbnailStatus::Failed {
+                            // Placeholder until the worker has rendered the thumbnail
+                            let placeholder = ui.add_sized([45.0, 60.0], egui::Spinner::new());
+                            if loaded_pdf.thumbnail_status == app::ThumbnailStatus::NotRequested
+                                && ui.is_rect_visible(placeholder.rect)
+                            {
+                                thumbnails_to_request.push(idx);
+                            }
                             ui.add_space(8.0);
                         }
 
                         ui.vertical(|ui| {
-                            ui.label(&loaded_pdf.file.filename);
-                            ui.weak(format!("{} pages", loaded_pdf.file.page_count));
+                            let has_javascript = javascript_paths
+                                .contains(&loaded_pdf.file.path.display().to_string());
+                            ui.horizontal(|ui| {
+                                match loaded_pdf.validation {
+                                    app::ValidationStatus::NotValidated => {}
+                                    app::ValidationStatus::Pending => {
+                                        ui.add(egui::Spinner::new().size(12.0));
+                                    }
+                                    app::ValidationStatus::Valid => {
+                                        ui.label(
+                                            egui::RichText::new("✓").color(egui::Color32::GREEN),
+                                        )
+                                        .on_hover_text("Opens without errors");
+                                    }
+                                    app::ValidationStatus::Invalid(ref reason) => {
+                                        ui.label(
+                                            egui::RichText::new("✗").color(egui::Color32::RED),
+                                        )
+                                        .on_hover_text(reason);
+                                    }
+                                }
+                                let name = ui.selectable_label(
+                                    self.current_pdf == Some(idx),
+                                    &loaded_pdf.file.filename,
+                                );
+                                if name.clicked() {
+                                    to_focus = Some(idx);
+                                }
+                                if let Some(ref hash) = loaded_pdf.file.content_hash {
+                                    name.on_hover_text(format!("SHA-256: {}…", &hash[..8]));
+                                }
+                                if has_javascript {
+                                    ui.label(
+                                        egui::RichText::new("⚠ JavaScript")
+                                            .color(egui::Color32::RED),
+                                    )
+                                    .on_hover_text("This PDF contains JavaScript actions");
+                                }
+                            });
+                            match loaded_pdf.warning {
+                                Some(ref warning) => {
+                                    ui.label(
+                                        egui::RichText::new(format!("⚠ {}", warning))
+                                            .color(egui::Color32::YELLOW),
+                                    );
+                                }
+                                None => {
+                                    ui.weak(format!("{} pages", loaded_pdf.file.page_count));
+                                }
+                            }
+                            ui.label(
+                                egui::RichText::new(file_details(&loaded_pdf.file))
+                                    .small()
+                                    .weak(),
+                            );
+
+                            let page_count = loaded_pdf.file.page_count;
+                            if page_count > 0 {
+                                let full = analyzer::PageRange {
+                                    first: 1,
+                                    last: page_count,
+                                };
+                                let mut range = loaded_pdf.page_range.unwrap_or(full);
+                                ui.horizontal(|ui| {
+                                    ui.label("Pages");
+                                    let mut changed = ui
+                                        .add(
+                                            egui::DragValue::new(&mut range.first)
+                                                .range(1..=page_count),
+                                        )
+                                        .changed();
+                                    ui.label("–");
+                                    changed |= ui
+                                        .add(
+                                            egui::DragValue::new(&mut range.last)
+                                                .range(1..=page_count),
+                                        )
+                                        .changed();
+                                    if changed {
+                                        range.last = range.last.max(range.first);
+                                        loaded_pdf.page_range = (range != full).then_some(range);
+                                    }
+                                });
+                            }
                         });
 
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui.button("🗑").clicked() {
                                 to_remove = Some(idx);
                             }
+                            if ui
+                                .add_enabled(
+                                    !is_analyzing && !reanalyzing.contains(&loaded_pdf.file.path),
+                                    egui::Button::new("↺"),
+                                )
+                                .on_hover_text("Re-analyze this file")
+                                .clicked()
+                            {
+                                to_reanalyze = Some(idx);
+                            }
+                            if ui
+                                .add_enabled(idx + 1 < pdf_count, egui::Button::new("⬇"))
+                                .on_hover_text("Move down")
+                                .clicked()
+                            {
+                                to_move = Some((idx, idx + 1));
+                            }
+                            if ui
+                                .add_enabled(idx > 0, egui::Button::new("⬆"))
+                                .on_hover_text("Move up")
+                                .clicked()
+                            {
+                                to_move = Some((idx, idx - 1));
+                            }
                         });
                     });
                     ui.add_space(8.0);
                 }
 
+                if let Some(idx) = to_toggle {
+                    self.toggle_selected(idx);
+                }
+                if let Some(idx) = to_focus {
+                    self.current_pdf = Some(idx);
+                }
                 if let Some(idx) = to_remove {
                     self.remove_pdf(idx);
                 }
+                if let Some((from, to)) = to_move {
+                    self.move_pdf(from, to);
+                }
+                if let Some(idx) = to_reanalyze {
+                    self.reanalyze_pdf(idx);
+                }
+                for idx in thumbnails_to_request {
+                    self.request_thumbnail(idx);
+                }
+                if let Some(idx) = to_preview {
+                    self.open_preview(idx);
+                }
             });
         }
     }
 
     fn show_results_tab(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        if self.output_data.is_empty() {
+        if self.analysis_results.is_empty() {
             ui.vertical_centered(|ui| {
                 ui.add_space(50.0);
                 ui.label(egui::RichText::new("No results yet").size(16.0).weak());
@@ -233,8 +830,160 @@ impl App {
             return;
         }
 
+        if self.output_data.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(50.0);
+                ui.label(
+                    egui::RichText::new("All outputs are disabled")
+                        .size(16.0)
+                        .weak(),
+                );
+                ui.label("Enable an output in ⚙ Settings to see results");
+            });
+            return;
+        }
+
+        if let (Some(started), Some(finished), Some(last_run)) = (
+            self.analysis_started_at,
+            self.analysis_finished_at,
+            self.last_run,
+        ) {
+            ui.weak(format!(
+                "Analyzed {} files in {:.1}s on {}",
+                self.analysis_results.len(),
+                finished.duration_since(started).as_secs_f64(),
+                last_run.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        if self.analysis_cancelled {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "⚠ The analysis was cancelled; files not analyzed yet are missing from the results",
+            );
+        }
+
+        // Signature status per file path, shown as a lock next to signed files
+        let signatures: Vec<(String, usize, bool)> = self
+            .analysis_results
+            .iter()
+            .flat_map(|result| {
+                result.results.iter().filter_map(|analysis| match analysis {
+                    analyzer::AnalysisResult::SignatureAnalysis {
+                        has_signatures: true,
+                        signature_count,
+                        is_certified,
+                    } => Some((result.path.clone(), *signature_count, *is_certified)),
+                    _ => None,
+                })
+            })
+            .collect();
+
+        // Exports covering all results rather than one output
+        ui.horizontal(|ui| {
+            if ui
+                .button("💾 Export JSON")
+                .on_hover_text("Ctrl+E")
+                .clicked()
+            {
+                self.export_results_json();
+            }
+            if ui.button("💾 Export HTML Report").clicked() {
+                self.export_html_report();
+            }
+            if ui.button("💾 Export Markdown").clicked() {
+                self.export_markdown_report();
+            }
+            ui.separator();
+            if ui
+                .button("📸 Save Snapshot")
+                .on_hover_text("Keep these results to compare with the next analysis")
+                .clicked()
+            {
+                self.save_snapshot();
+            }
+            if ui
+                .button("📂 Compare with JSON...")
+                .on_hover_text("Compare with an analysis saved by Export JSON")
+                .clicked()
+            {
+                self.open_comparison();
+            }
+            if !self.comparison_baseline.is_empty() {
+                ui.label(format!(
+                    "Comparing with {} file(s)",
+                    self.comparison_baseline.len()
+                ));
+                if ui.small_button("✖").clicked() {
+                    self.clear_comparison();
+                }
+            }
+        });
+        ui.add_space(8.0);
+
+        if self.app_config.show_timing {
+            let timings = self.analyzer_timing_averages();
+            if !timings.is_empty() {
+                ui.collapsing("⏱ Analyzer timings", |ui| {
+                    egui::Grid::new("analyzer_timings")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Analyzer");
+                            ui.strong("Average per file");
+                            ui.end_row();
+                            for (name, ms) in &timings {
+                                ui.label(name);
+                                ui.label(format!("{:.0} ms", ms));
+                                ui.end_row();
+                            }
+                        });
+                    ui.weak("Disable slow analyzers you don't need in ⚙ Settings");
+                });
+                ui.add_space(8.0);
+            }
+        }
+
+        // Failed analyzers leave gaps in the numbers, so they are listed with the results
+        let warning_count: usize = self.analysis_results.iter().map(|r| r.errors.len()).sum();
+        if warning_count > 0 {
+            egui::CollapsingHeader::new(format!("⚠ Warnings ({})", warning_count))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for result in &self.analysis_results {
+                        if result.errors.is_empty() {
+                            continue;
+                        }
+                        ui.strong(&result.filename);
+                        for error in &result.errors {
+                            ui.label(format!("  • {}", error));
+                        }
+                    }
+                });
+            ui.add_space(8.0);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("🔍 Filter files");
+            let filter = ui.add(
+                egui::TextEdit::singleline(&mut self.results_filter)
+                    .hint_text("part of a file name"),
+            );
+            if filter.changed() {
+                self.regenerate_outputs();
+            }
+            if !self.results_filter.is_empty() && ui.small_button("✖").clicked() {
+                self.results_filter.clear();
+                self.regenerate_outputs();
+            }
+        });
+        ui.add_space(8.0);
+
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for output in &self.output_data {
+            let mut save_index = None;
+            let mut export_index = None;
+            let mut sort_clicked = None;
+            let mut detail_index = None;
+
+            for (idx, output) in self.output_data.iter().enumerate() {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
                         ui.heading(&output.title);
@@ -242,6 +991,12 @@ impl App {
                             if ui.button("📋 Copy").clicked() {
                                 ctx.copy_text(output.copyable_text.clone());
                             }
+                            if ui.button("💾 Save").clicked() {
+                                save_index = Some(idx);
+                            }
+                            if ui.button("💾 Export CSV").clicked() {
+                                export_index = Some(idx);
+                            }
                         });
                     });
 
@@ -252,17 +1007,108 @@ impl App {
                             .striped(true)
                             .min_col_width(80.0)
                             .show(ui, |ui| {
-                                // Header row
-                                for col in &output.columns {
-                                    ui.strong(col);
+                                let sort = self.table_sort.get(&output.title).copied();
+
+                                // Header row, click to sort
+                                for (col_idx, col) in output.columns.iter().enumerate() {
+                                    let text = match sort {
+                                        Some(s) if s.column == col_idx && s.ascending => {
+                                            format!("{} ⏶", col)
+                                        }
+                                        Some(s) if s.column == col_idx => format!("{} ⏷", col),
+                                        _ => col.clone(),
+                                    };
+                                    let header = ui
+                                        .add(
+                                            egui::Label::new(egui::RichText::new(text).strong())
+                                                .sense(egui::Sense::click()),
+                                        )
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                                    if header.clicked() {
+                                        sort_clicked = Some((output.title.clone(), col_idx));
+                                    }
                                 }
                                 ui.end_row();
 
+                                let mut rows: Vec<&output::OutputRow> =
+                                    output.per_pdf.iter().collect();
+                                if let Some(sort) = sort {
+                                    let cell = |row: &output::OutputRow| match sort.column {
+                                        0 => row.filename.clone(),
+                                        n => row
+                                            .values
+                                            .get(n - 1)
+                                            .map(|(_, value)| value.clone())
+                                            .unwrap_or_default(),
+                                    };
+                                    rows.sort_by(|a, b| {
+                                        let ordering = output::compare_values(&cell(a), &cell(b));
+                                        if sort.ascending {
+                                            ordering
+                                        } else {
+                                            ordering.reverse()
+                                        }
+                                    });
+                                }
+
                                 // Data rows
-                                for row in &output.per_pdf {
-                                    ui.label(&row.filename);
+                                for row in rows {
+                                    // Not every output has one row per result, so the
+                                    // detail view is found by path
+                                    let result_index = row.path.as_ref().and_then(|path| {
+                                        self.analysis_results
+                                            .iter()
+                                            .position(|result| result.path == *path)
+                                    });
+                                    let (name, hover) = match signatures
+                                        .iter()
+                                        .find(|(path, _, _)| row.path.as_ref() == Some(path))
+                                    {
+                                        Some((_, count, certified)) => (
+                                            format!("🔒 {}", row.filename),
+                                            format!(
+                                                "{} signature(s){}\nClick to show its pages",
+                                                count,
+                                                if *certified { ", certified" } else { "" }
+                                            ),
+                                        ),
+                                        None => (
+                                            row.filename.clone(),
+                                            "Click to show its pages".to_string(),
+                                        ),
+                                    };
+                                    let color = row.highlight.then(|| ui.visuals().warn_fg_color);
+                                    let name = if row.highlight {
+                                        format!("⚠ {}", name)
+                                    } else {
+                                        name
+                                    };
+                                    let mut name = egui::RichText::new(name);
+                                    if let Some(color) = color {
+                                        name = name.color(color);
+                                    }
+                                    match result_index {
+                                        Some(index) => {
+                                            let name = ui
+                                                .add(
+                                                    egui::Label::new(name)
+                                                        .sense(egui::Sense::click()),
+                                                )
+                                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                                .on_hover_text(hover);
+                                            if name.clicked() {
+                                                detail_index = Some(index);
+                                            }
+                                        }
+                                        None => {
+                                            ui.label(name);
+                                        }
+                                    }
                                     for (_, value) in &row.values {
-                                        ui.label(value);
+                                        match color {
+                                            Some(color) => ui.colored_label(color, value),
+                                            None => ui.label(value),
+                                        };
                                     }
                                     ui.end_row();
                                 }
@@ -285,6 +1131,19 @@ impl App {
                 ui.add_space(16.0);
             }
 
+            if let Some(idx) = save_index {
+                self.save_output(idx);
+            }
+            if let Some(idx) = export_index {
+                self.export_output_csv(idx);
+            }
+            if let Some((title, column)) = sort_clicked {
+                self.sort_table(&title, column);
+            }
+            if let Some(idx) = detail_index {
+                self.open_file_detail(idx);
+            }
+
             ui.add_space(16.0);
             ui.separator();
             ui.add_space(8.0);
@@ -297,30 +1156,572 @@ impl App {
         });
     }
 
-    fn show_settings_window(&mut self, ctx: &egui::Context) {
-        let mut show_settings = self.show_settings;
+    fn show_file_detail_tab(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, index: usize) {
+        let (Some(result), Some(detail)) =
+            (self.analysis_results.get(index), self.file_detail.as_mut())
+        else {
+            self.close_file_detail();
+            return;
+        };
 
-        egui::Window::new("Settings")
-            .open(&mut show_settings)
-            .resizable(true)
-            .default_width(400.0)
-            .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    let mut config_changed = false;
+        // Textures are created on first display, the images aren't needed after that
+        for (page, image) in detail.rendered.drain() {
+            let texture = ctx.load_texture(
+                format!("pdf_detail_{}", page),
+                color_image(&image),
+                egui::TextureOptions::LINEAR,
+            );
+            detail.textures.insert(page, texture);
+        }
 
-                    // Analyzer settings
-                    ui.collapsing("Analyzers", |ui| {
-                        let analyzer_params = self.analyzer_registry.all_config_params();
-                        if analyzer_params.is_empty() {
-                            ui.label("No configurable analyzers");
-                        } else {
-                            for (id, name, params) in analyzer_params {
-                                ui.group(|ui| {
-                                    ui.strong(name);
-                                    for param in params {
-                                        config_changed |=
-                                            self.render_config_param(ui, id, &param, true);
-                                    }
+        let mut back = false;
+        ui.horizontal(|ui| {
+            back = ui.button("← Back").clicked();
+            ui.heading(&result.filename);
+        });
+        if let Some(range) = result.page_range {
+            ui.weak(format!(
+                "Pages {}–{} were analyzed; color and blank status of the others is unknown",
+                range.first, range.last
+            ));
+        }
+        ui.add_space(8.0);
+
+        // Per-page results of the analyzers, by 1-based page number
+        let mut color_pages: Option<&[usize]> = None;
+        let mut color_results: Option<&[(usize, bool)]> = None;
+        let mut blank_pages: Option<&[usize]> = None;
+        for analysis in &result.results {
+            match analysis {
+                analyzer::AnalysisResult::ColorAnalysis {
+                    color_page_numbers, ..
+                } => color_pages = Some(color_page_numbers),
+                analyzer::AnalysisResult::ColorAnalysisDetailed { page_results, .. } => {
+                    color_results = Some(page_results)
+                }
+                analyzer::AnalysisResult::BlankPages {
+                    blank_page_indices, ..
+                } => blank_pages = Some(blank_page_indices),
+                _ => {}
+            }
+        }
+        let is_color = |number: usize| match (color_results, color_pages) {
+            (Some(results), _) => results
+                .iter()
+                .find(|(page, _)| *page == number)
+                .map(|(_, color)| *color),
+            (None, Some(pages)) if result.analyzed_page(number) => Some(pages.contains(&number)),
+            _ => None,
+        };
+        let is_blank = |number: usize| match blank_pages {
+            Some(pages) if result.analyzed_page(number) => Some(pages.contains(&number)),
+            _ => None,
+        };
+
+        let mut thumbnails_to_request = Vec::new();
+        match detail.pages {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Reading pages...");
+                });
+            }
+            Some(Err(ref error)) => {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            Some(Ok(ref pages)) => {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (page_idx, page) in pages.iter().enumerate() {
+                        let number = page_idx + 1;
+                        ui.horizontal(|ui| {
+                            match detail.textures.get(&page_idx) {
+                                Some(texture) => {
+                                    ui.image((texture.id(), texture.size_vec2()));
+                                }
+                                None if detail.failed.contains(&page_idx) => {
+                                    ui.add_sized([80.0, 110.0], egui::Label::new("—"));
+                                }
+                                None => {
+                                    let placeholder =
+                                        ui.add_sized([80.0, 110.0], egui::Spinner::new());
+                                    if ui.is_rect_visible(placeholder.rect) {
+                                        thumbnails_to_request.push(page_idx);
+                                    }
+                                }
+                            }
+                            ui.add_space(8.0);
+
+                            ui.vertical(|ui| {
+                                ui.strong(format!("Page {}", number));
+                                ui.label(format!(
+                                    "{:.0} × {:.0} mm",
+                                    page.width_mm, page.height_mm
+                                ));
+                                if page.rotation != 0 {
+                                    ui.label(format!("Rotated {}°", page.rotation));
+                                }
+                                ui.label(format!("{} words", page.words));
+                                match is_color(number) {
+                                    Some(true) => ui.label("Color"),
+                                    Some(false) => ui.label("B&W"),
+                                    None => ui.weak("Color not analyzed"),
+                                };
+                                if is_blank(number) == Some(true) {
+                                    ui.label("Blank");
+                                }
+                            });
+                        });
+                        ui.add_space(8.0);
+                    }
+                });
+            }
+        }
+
+        for page in thumbnails_to_request {
+            self.request_detail_thumbnail(page);
+        }
+        if back {
+            self.close_file_detail();
+        }
+    }
+
+    fn show_compare_tab(&mut self, ui: &mut egui::Ui) {
+        let Some((before, after)) = &self.comparison_results else {
+            self.current_tab = app::AppTab::Results;
+            return;
+        };
+
+        let mut discard = false;
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Snapshot: {} file(s), current results: {} file(s)",
+                before.len(),
+                after.len()
+            ));
+            if ui.button("🗑 Discard Snapshot").clicked() {
+                discard = true;
+            }
+        });
+        ui.weak("Changed values are shown in orange, before on the left and after on the right");
+        ui.add_space(8.0);
+
+        let changed = egui::Color32::ORANGE;
+        let cell = |ui: &mut egui::Ui, value: Option<&String>, other: Option<&String>| {
+            let text = value.map_or("—", |v| v.as_str());
+            if value != other {
+                ui.colored_label(changed, text);
+            } else {
+                ui.label(text);
+            }
+        };
+
+        egui::ScrollArea::both().show(ui, |ui| {
+            for output in &self.comparison {
+                ui.group(|ui| {
+                    ui.heading(&output.title);
+                    ui.add_space(8.0);
+
+                    if !output.rows.is_empty() {
+                        egui::Grid::new(format!("compare_{}", output.title))
+                            .striped(true)
+                            .min_col_width(60.0)
+                            .show(ui, |ui| {
+                                // Skip the "File" column, every value gets a before and after column
+                                ui.strong("File");
+                                for column in output.columns.iter().skip(1) {
+                                    ui.strong(format!("{} (before)", column));
+                                    ui.strong(format!("{} (after)", column));
+                                }
+                                ui.end_row();
+
+                                for row in &output.rows {
+                                    ui.label(&row.filename);
+                                    for col in 0..output.columns.len().saturating_sub(1) {
+                                        let before = row.before.as_ref().and_then(|v| v.get(col));
+                                        let after = row.after.as_ref().and_then(|v| v.get(col));
+                                        cell(ui, before, after);
+                                        cell(ui, after, before);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        ui.add_space(8.0);
+                        ui.separator();
+                    }
+
+                    ui.add_space(4.0);
+                    for (label, before, after) in &output.totals {
+                        ui.horizontal(|ui| {
+                            ui.strong(format!("{}:", label));
+                            cell(ui, before.as_ref(), Some(after));
+                            ui.label("→");
+                            cell(ui, Some(after), before.as_ref());
+                        });
+                    }
+                });
+                ui.add_space(16.0);
+            }
+        });
+
+        if discard {
+            self.discard_snapshot();
+        }
+    }
+
+    fn save_output(&mut self, index: usize) {
+        let Some(output) = self.output_data.get(index) else {
+            return;
+        };
+
+        let extension = output.file_extension;
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter(format!("{} files", extension.to_uppercase()), &[extension])
+            .set_title("Save results")
+            .set_file_name(format!("{}.{}", output_file_stem(&output.title), extension));
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            if let Err(e) = std::fs::write(&path, &output.copyable_text) {
+                self.errors
+                    .push(format!("Failed to save '{}': {}", path.display(), e));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    fn export_output_csv(&mut self, index: usize) {
+        let Some(output) = self.output_data.get(index) else {
+            return;
+        };
+
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_title("Export CSV")
+            .set_file_name(format!("{}.csv", output_file_stem(&output.title)));
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            let run = self.analysis_run();
+            if let Err(e) = export::csv::write_output_csv(output, run.as_ref(), &path) {
+                self.errors
+                    .push(format!("Failed to export '{}': {}", path.display(), e));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    fn export_results_json(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_title("Export JSON")
+            .set_file_name("analysis_results.json");
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            let run = self.analysis_run();
+            if let Err(e) =
+                export::json::write_results_json(&self.analysis_results, run.as_ref(), &path)
+            {
+                self.errors
+                    .push(format!("Failed to export '{}': {}", path.display(), e));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    fn open_comparison(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_title("Compare with saved analysis");
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.pick_file() {
+            if let Err(e) = self.load_comparison(&path) {
+                self.errors.push(e.to_string());
+            }
+        }
+    }
+
+    fn export_html_report(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("HTML", &["html"])
+            .set_title("Export HTML Report")
+            .set_file_name("analysis_report.html");
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            let mut report = output::html::HtmlOutput::default();
+            report.apply_config(&self.config);
+            report.set_thumbnails(&self.thumbnail_data_uris());
+            report.set_cancelled(self.analysis_cancelled);
+            if let Err(e) = report.export_to_html(&self.analysis_results, &path) {
+                self.errors
+                    .push(format!("Failed to export '{}': {}", path.display(), e));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    fn export_markdown_report(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("Markdown", &["md"])
+            .set_title("Export Markdown")
+            .set_file_name("analysis_report.md");
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            let mut report = output::markdown::MarkdownOutput::default();
+            report.apply_config(&self.config);
+            report.set_cancelled(self.analysis_cancelled);
+            if let Err(e) = report.export_to_markdown(&self.analysis_results, &path) {
+                self.errors
+                    .push(format!("Failed to export '{}': {}", path.display(), e));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    fn show_preview_window(&mut self, ctx: &egui::Context) {
+        let Some(ref mut preview) = self.preview else {
+            return;
+        };
+
+        // Textures are created on first display, the images aren't needed after that
+        for (page, image) in preview.rendered.drain() {
+            let texture = ctx.load_texture(
+                format!("pdf_preview_{}", page),
+                color_image(&image),
+                egui::TextureOptions::LINEAR,
+            );
+            preview.textures.insert(page, texture);
+        }
+
+        let mut open = true;
+        egui::Window::new(format!("Preview: {}", preview.filename))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([500.0, 700.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(preview.page > 0, egui::Button::new("◀ Prev"))
+                        .clicked()
+                    {
+                        preview.page -= 1;
+                    }
+                    ui.label(format!(
+                        "Page {} / {}",
+                        preview.page + 1,
+                        preview.page_count
+                    ));
+                    if ui
+                        .add_enabled(
+                            preview.page + 1 < preview.page_count,
+                            egui::Button::new("Next ▶"),
+                        )
+                        .clicked()
+                    {
+                        preview.page += 1;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::both().show(ui, |ui| {
+                    if let Some(texture) = preview.textures.get(&preview.page) {
+                        // Fit the page to the window width
+                        let size = texture.size_vec2();
+                        let scale = (ui.available_width() / size.x).min(1.0);
+                        ui.image((texture.id(), size * scale));
+                    } else if let Some(error) = preview.errors.get(&preview.page) {
+                        ui.colored_label(egui::Color32::RED, error);
+                    } else {
+                        ui.spinner();
+                    }
+                });
+            });
+
+        if !open {
+            self.preview = None;
+        }
+    }
+
+    fn show_password_window(&mut self, ctx: &egui::Context) {
+        let waiting = self.password_prompts.len() - 1;
+        let Some(prompt) = self.password_prompts.front_mut() else {
+            return;
+        };
+
+        let filename = prompt
+            .path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| prompt.path.display().to_string());
+        let mut submit = false;
+        let mut skip = false;
+
+        egui::Window::new("Password required")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "'{}' is encrypted. Enter its user or owner password to open it.",
+                    filename
+                ));
+                ui.add_space(4.0);
+
+                let response =
+                    ui.add(egui::TextEdit::singleline(&mut prompt.password).password(true));
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    submit = true;
+                }
+                ui.checkbox(&mut prompt.remember, "Remember for this session");
+
+                if let Some(ref error) = prompt.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                match prompt.checking {
+                    Some(app::PasswordSource::Session) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Trying the remembered passwords...");
+                        });
+                    }
+                    Some(app::PasswordSource::Entered) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Opening...");
+                        });
+                    }
+                    None => {}
+                }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(prompt.checking.is_none(), egui::Button::new("Open"))
+                        .clicked()
+                    {
+                        submit = true;
+                    }
+                    if ui.button("Skip").clicked() {
+                        skip = true;
+                    }
+                });
+
+                if waiting > 0 {
+                    ui.weak(format!("{} more encrypted files waiting", waiting));
+                }
+            });
+
+        if submit {
+            self.submit_password();
+        } else if skip {
+            self.skip_password();
+        }
+    }
+
+    fn show_new_profile_window(&mut self, ctx: &egui::Context) {
+        let mut create = false;
+        let mut cancel = false;
+
+        egui::Window::new("New Profile")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Starts with the settings of \"{}\"",
+                    self.app_config.active_profile
+                ));
+                ui.add_space(4.0);
+
+                if let Some(ref mut name) = self.new_profile_name {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(name)
+                            .hint_text("Profile name")
+                            .desired_width(250.0),
+                    );
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        create = true;
+                    }
+                }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Create").clicked() {
+                        create = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if create {
+            let name = self.new_profile_name.clone().unwrap_or_default();
+            match self.create_profile(&name) {
+                Ok(()) => self.new_profile_name = None,
+                Err(e) => self.errors.push(e.to_string()),
+            }
+        } else if cancel {
+            self.new_profile_name = None;
+        }
+    }
+
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
+        let mut show_settings = self.show_settings;
+
+        egui::Window::new("Settings")
+            .open(&mut show_settings)
+            .resizable(true)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Profile:");
+                    self.render_profile_selector(ui, "settings_profile");
+                });
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Export settings").clicked() {
+                        self.export_settings();
+                    }
+                    if ui.button("Import settings").clicked() {
+                        self.import_settings();
+                    }
+                });
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let mut config_changed = false;
+
+                    // Analyzer settings
+                    ui.collapsing("Analyzers", |ui| {
+                        let analyzer_params = self.analyzer_registry.all_config_params();
+                        if analyzer_params.is_empty() {
+                            ui.label("No configurable analyzers");
+                        } else {
+                            for (id, name, params) in analyzer_params {
+                                ui.group(|ui| {
+                                    ui.strong(name);
+                                    config_changed |= self.render_enabled_toggle(ui, id);
+                                    for param in params {
+                                        config_changed |=
+                                            self.render_config_param(ui, id, &param, true);
+                                    }
                                 });
                             }
                         }
@@ -334,6 +1735,7 @@ impl App {
                         for (id, name, params) in output_params {
                             ui.group(|ui| {
                                 ui.strong(name);
+                                config_changed |= self.render_enabled_toggle(ui, id);
                                 for param in params {
                                     config_changed |=
                                         self.render_config_param(ui, id, &param, false);
@@ -342,8 +1744,172 @@ impl App {
                         }
                     });
 
+                    ui.add_space(8.0);
+
+                    ui.collapsing("Appearance", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Font size");
+                            let response = ui.add(
+                                egui::Slider::new(
+                                    &mut self.app_config.font_scale,
+                                    config::FONT_SCALE_RANGE,
+                                )
+                                .step_by(0.05)
+                                .suffix("×"),
+                            );
+                            if response.changed() {
+                                apply_font_scale(ctx, self.app_config.font_scale);
+                                config_changed = true;
+                            }
+                            if ui
+                                .add_enabled(
+                                    self.app_config.font_scale != config::DEFAULT_FONT_SCALE,
+                                    egui::Button::new("Reset"),
+                                )
+                                .clicked()
+                            {
+                                self.app_config.font_scale = config::DEFAULT_FONT_SCALE;
+                                apply_font_scale(ctx, self.app_config.font_scale);
+                                config_changed = true;
+                            }
+                        });
+                        ui.weak("Size of all text relative to egui's default");
+
+                        ui.horizontal(|ui| {
+                            ui.label("Theme");
+                            let before = self.app_config.theme.clone();
+                            egui::ComboBox::from_id_salt("theme")
+                                .selected_text(match before.as_str() {
+                                    "light" => "Light",
+                                    "dark" => "Dark",
+                                    _ => "System",
+                                })
+                                .show_ui(ui, |ui| {
+                                    let theme = &mut self.app_config.theme;
+                                    ui.selectable_value(theme, "system".to_string(), "System");
+                                    ui.selectable_value(theme, "light".to_string(), "Light");
+                                    ui.selectable_value(theme, "dark".to_string(), "Dark");
+                                });
+                            if self.app_config.theme != before {
+                                ctx.set_theme(theme_preference(&self.app_config.theme));
+                                config_changed = true;
+                            }
+                        });
+                    });
+
+                    ui.add_space(8.0);
+
+                    // App-wide settings
+                    ui.collapsing("General", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Max folder depth");
+                            config_changed |= ui
+                                .add(
+                                    egui::DragValue::new(&mut self.app_config.max_folder_depth)
+                                        .range(0..=64),
+                                )
+                                .changed();
+                        });
+                        ui.weak("How many subfolder levels \"Add Folder\" searches for PDFs");
+
+                        let mut thumbnails_changed = false;
+                        for (label, value, max) in [
+                            ("Thumbnail width", &mut self.app_config.thumbnail_width, 600),
+                            (
+                                "Thumbnail max height",
+                                &mut self.app_config.thumbnail_max_height,
+                                800,
+                            ),
+                        ] {
+                            ui.horizontal(|ui| {
+                                ui.label(label);
+                                let response = ui.add(egui::DragValue::new(value).range(32..=max));
+                                config_changed |= response.changed();
+                                // Render once the drag ends instead of on every step
+                                thumbnails_changed |= response.drag_stopped()
+                                    || (response.changed() && !response.dragged());
+                            });
+                        }
+                        if thumbnails_changed {
+                            self.refresh_thumbnails();
+                        }
+                        config_changed |= ui
+                            .checkbox(
+                                &mut self.app_config.use_analysis_cache,
+                                "Reuse results of unchanged files",
+                            )
+                            .changed();
+                        ui.weak("Turn off to force a fresh analysis of every file");
+                        ui.horizontal(|ui| {
+                            ui.label("Parallel analyses");
+                            config_changed |= ui
+                                .add(
+                                    egui::DragValue::new(&mut self.app_config.analysis_workers)
+                                        .range(0..=64),
+                                )
+                                .changed();
+                        });
+                        ui.weak("Files analyzed at once, each in its own process; 0 uses one per CPU core, up to 4");
+                        config_changed |= ui
+                            .checkbox(&mut self.app_config.show_timing, "Show analyzer timings")
+                            .changed();
+                        ui.weak("List the average time per file of each analyzer in the results");
+                    });
+
+                    ui.add_space(8.0);
+
+                    // Folder watching
+                    ui.collapsing("Watch folder", |ui| {
+                        let mut watch_changed = false;
+                        watch_changed |= ui
+                            .checkbox(
+                                &mut self.app_config.watch_enabled,
+                                "Analyze new PDFs in folder",
+                            )
+                            .on_hover_text(format!(
+                                "Results are appended to {} in the watched folder",
+                                watch::LOG_FILE_NAME
+                            ))
+                            .changed();
+                        ui.horizontal(|ui| {
+                            let folder = self
+                                .app_config
+                                .watch_folder
+                                .as_ref()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_else(|| "No folder selected".to_string());
+                            ui.label(folder);
+                            if ui.button("Choose...").clicked() {
+                                if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                    self.app_config.watch_folder = Some(folder);
+                                    watch_changed = true;
+                                }
+                            }
+                        });
+                        if watch_changed {
+                            config_changed = true;
+                            self.update_watcher();
+                        }
+                    });
+
+                    ui.collapsing("Keyboard Shortcuts", |ui| {
+                        egui::Grid::new("keyboard_shortcuts")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (keys, action) in SHORTCUTS {
+                                    ui.monospace(*keys);
+                                    ui.label(*action);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
                     if config_changed {
                         self.save_config();
+                        // Show output settings and toggles on the current results right away
+                        if !self.analysis_results.is_empty() {
+                            self.regenerate_outputs();
+                        }
                     }
                 });
             });
@@ -351,6 +1917,120 @@ impl App {
         self.show_settings = show_settings;
     }
 
+    /// Stack the live toasts upwards from the bottom-right corner.
+    fn show_toasts(&mut self, ctx: &egui::Context) {
+        let now = std::time::Instant::now();
+        self.toasts.retain(|toast| toast.expires_at > now);
+
+        let mut fading = false;
+        for (idx, toast) in self.toasts.iter().enumerate() {
+            let remaining = toast.expires_at.saturating_duration_since(now);
+            let opacity = (remaining.as_secs_f32() / app::TOAST_FADE.as_secs_f32()).min(1.0);
+            fading |= opacity < 1.0;
+
+            let visuals = &ctx.style().visuals;
+            let color = match toast.kind {
+                app::ToastKind::Info => egui::Color32::from_rgb(0x3c, 0xb3, 0x71),
+                app::ToastKind::Warn => visuals.warn_fg_color,
+                app::ToastKind::Error => visuals.error_fg_color,
+            };
+
+            egui::Window::new("toast")
+                .id(egui::Id::new(("toast", idx)))
+                .title_bar(false)
+                .resizable(false)
+                .frame(egui::Frame::window(&ctx.style()).multiply_with_opacity(opacity))
+                .anchor(
+                    egui::Align2::RIGHT_BOTTOM,
+                    egui::vec2(-16.0, -64.0 - idx as f32 * 48.0),
+                )
+                .show(ctx, |ui| {
+                    ui.multiply_opacity(opacity);
+                    ui.colored_label(color, &toast.message);
+                });
+        }
+
+        // Animate the fade, otherwise wake up again once the next one starts fading
+        if fading {
+            ctx.request_repaint();
+        } else if let Some(next) = self.toasts.iter().map(|toast| toast.expires_at).min() {
+            ctx.request_repaint_after(next.saturating_duration_since(now + app::TOAST_FADE));
+        }
+    }
+
+    /// Dropdown of the profiles that switches to the picked one.
+    fn render_profile_selector(&mut self, ui: &mut egui::Ui, id_salt: &str) {
+        let mut selected = self.app_config.active_profile.clone();
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(&selected)
+            .show_ui(ui, |ui| {
+                for name in self.app_config.profile_names() {
+                    ui.selectable_value(&mut selected, name.clone(), name);
+                }
+            });
+        if selected != self.app_config.active_profile {
+            self.switch_profile(&selected);
+        }
+    }
+
+    /// Save the active profile's analyzer and output settings to a file.
+    fn export_settings(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("TOML", &["toml"])
+            .set_title("Export settings")
+            .set_file_name(format!(
+                "{}.toml",
+                output_file_stem(&self.app_config.active_profile)
+            ));
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.save_file() {
+            if let Err(e) = self.config.export_to(&path) {
+                self.errors.push(format!(
+                    "Failed to export settings to '{}': {}",
+                    path.display(),
+                    e
+                ));
+            }
+            self.last_save_directory = path.parent().map(|p| p.to_path_buf());
+        }
+    }
+
+    /// Replace the active profile's settings with ones from a file.
+    fn import_settings(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("TOML", &["toml"])
+            .set_title("Import settings");
+        if let Some(ref dir) = self.last_save_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.pick_file() {
+            match config::Config::import_from(&path) {
+                Ok(config) => {
+                    self.config = config;
+                    self.reject_invalid_options();
+                    self.save_config();
+                }
+                Err(e) => self
+                    .errors
+                    .push(format!("Failed to import settings: {}", e)),
+            }
+        }
+    }
+
+    /// "Enabled" checkbox of an analyzer or output; returns whether it changed.
+    fn render_enabled_toggle(&mut self, ui: &mut egui::Ui, module_id: &str) -> bool {
+        let mut enabled = self.config.is_enabled(module_id);
+        let changed = ui.checkbox(&mut enabled, "Enabled").changed();
+        if changed {
+            self.config.set_enabled(module_id, enabled);
+        }
+        changed
+    }
+
     fn render_config_param(
         &mut self,
         ui: &mut egui::Ui,
@@ -358,84 +2038,137 @@ impl App {
         param: &config::ConfigParam,
         is_analyzer: bool,
     ) -> bool {
-        let mut changed = false;
+        let current = if is_analyzer {
+            self.config.get_analyzer_value(module_id, param.key)
+        } else {
+            self.config.get_output_value(module_id, param.key)
+        }
+        .cloned();
+        let mut new_value = None;
 
         ui.horizontal(|ui| {
-            ui.label(param.label);
+            let label = ui.label(param.label);
+            if !param.description.is_empty() {
+                label.on_hover_text(param.description);
+            }
             ui.add_space(8.0);
 
             match &param.default {
                 config::ConfigValue::Bool(default) => {
-                    let current = if is_analyzer {
-                        self.config.get_analyzer_value(module_id, param.key)
-                    } else {
-                        self.config.get_output_value(module_id, param.key)
-                    }
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(*default);
-
-                    let mut value = current;
+                    let mut value = current.and_then(|v| v.as_bool()).unwrap_or(*default);
                     if ui.checkbox(&mut value, "").changed() {
-                        if is_analyzer {
-                            self.config.set_analyzer_value(
-                                module_id,
-                                param.key,
-                                config::ConfigValue::Bool(value),
-                            );
-                        } else {
-                            self.config.set_output_value(
-                                module_id,
-                                param.key,
-                                config::ConfigValue::Bool(value),
-                            );
-                        }
-                        changed = true;
+                        new_value = Some(config::ConfigValue::Bool(value));
                     }
                 }
                 config::ConfigValue::Float(default) => {
-                    let current = if is_analyzer {
-                        self.config.get_analyzer_value(module_id, param.key)
-                    } else {
-                        self.config.get_output_value(module_id, param.key)
+                    let mut value = current.and_then(|v| v.as_float()).unwrap_or(*default);
+                    let min = param.min.as_ref().and_then(|v| v.as_float());
+                    let max = param.max.as_ref().and_then(|v| v.as_float());
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut value)
+                                .speed(param.step.unwrap_or(0.01))
+                                .range(
+                                    min.unwrap_or(f64::NEG_INFINITY)..=max.unwrap_or(f64::INFINITY),
+                                ),
+                        )
+                        .changed()
+                    {
+                        new_value = Some(config::ConfigValue::Float(value));
                     }
-                    .and_then(|v| v.as_float())
-                    .unwrap_or(*default);
-
-                    let mut value = current;
+                }
+                config::ConfigValue::Int(default) => {
+                    let mut value = current.and_then(|v| v.as_int()).unwrap_or(*default);
+                    let min = param.min.as_ref().and_then(|v| v.as_int());
+                    let max = param.max.as_ref().and_then(|v| v.as_int());
                     if ui
                         .add(
                             egui::DragValue::new(&mut value)
-                                .speed(0.01)
-                                .range(0.0..=1000.0),
+                                .speed(param.step.unwrap_or(1.0))
+                                .range(min.unwrap_or(i64::MIN)..=max.unwrap_or(i64::MAX)),
                         )
                         .changed()
                     {
-                        if is_analyzer {
-                            self.config.set_analyzer_value(
-                                module_id,
-                                param.key,
-                                config::ConfigValue::Float(value),
-                            );
-                        } else {
-                            self.config.set_output_value(
-                                module_id,
-                                param.key,
-                                config::ConfigValue::Float(value),
-                            );
+                        new_value = Some(config::ConfigValue::Int(value));
+                    }
+                }
+                config::ConfigValue::String(default) => {
+                    let mut value = current
+                        .as_ref()
+                        .and_then(|v| v.as_string())
+                        .unwrap_or(default)
+                        .to_string();
+                    if let Some(ref options) = param.options {
+                        let before = value.clone();
+                        egui::ComboBox::from_id_salt((module_id, param.key))
+                            .selected_text(&value)
+                            .show_ui(ui, |ui| {
+                                for option in options {
+                                    ui.selectable_value(&mut value, option.to_string(), *option);
+                                }
+                            });
+                        if value != before {
+                            new_value = Some(config::ConfigValue::String(value));
                         }
-                        changed = true;
+                    } else if ui.text_edit_singleline(&mut value).changed() {
+                        new_value = Some(config::ConfigValue::String(value));
                     }
                 }
-                _ => {
-                    ui.label("(unsupported type)");
+                config::ConfigValue::List(default) => {
+                    // Edited as comma separated text. Items are kept untrimmed so
+                    // the text round-trips while typing; readers trim them.
+                    let mut value = current
+                        .as_ref()
+                        .and_then(|v| v.as_list())
+                        .unwrap_or(default)
+                        .join(",");
+                    if ui.text_edit_singleline(&mut value).changed() {
+                        let items = if value.is_empty() {
+                            vec![]
+                        } else {
+                            value.split(',').map(|s| s.to_string()).collect()
+                        };
+                        new_value = Some(config::ConfigValue::List(items));
+                    }
                 }
             }
         });
 
-        if !param.description.is_empty() {
-            ui.weak(param.description);
+        let changed = new_value.is_some();
+        if let Some(value) = new_value {
+            if is_analyzer {
+                self.config.set_analyzer_value(module_id, param.key, value);
+            } else {
+                self.config.set_output_value(module_id, param.key, value);
+            }
         }
 
         changed
     }
 }
+
+fn color_image(image: &image::RgbaImage) -> egui::ColorImage {
+    let size = [image.width() as usize, image.height() as usize];
+    egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice())
+}
+
+/// Size and modification date of a listed PDF, e.g. "2.4 MB · Modified 2024-11-15".
+fn file_details(file: &pdf::PdfFile) -> String {
+    let size = output::format_size(file.file_size_bytes);
+    match file.modified_at {
+        Some(modified) => {
+            let date = chrono::DateTime::<chrono::Local>::from(modified);
+            format!("{} · Modified {}", size, date.format("%Y-%m-%d"))
+        }
+        None => size,
+    }
+}
+
+/// File name for an output without extension: "Cost Calculation" -> "cost_calculation"
+fn output_file_stem(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
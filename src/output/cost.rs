@@ -5,6 +5,16 @@ use super::{OutputData, OutputModule, OutputRow};
 pub struct CostOutput {
     cost_bw: f64,
     cost_color: f64,
+    xfa_surcharge: f64,
+    attachment_fee: f64,
+    color_conversion_fee: f64,
+    skip_blank_pages: bool,
+    coverage_based: bool,
+    coverage_rate_bw: f64,
+    coverage_rate_color: f64,
+    color_coverage_billing_threshold: f64,
+    /// Files costing more are highlighted, 0.0 disables it
+    highlight_cost_above: f64,
     show_per_pdf: bool,
 }
 
@@ -13,6 +23,15 @@ impl Default for CostOutput {
         Self {
             cost_bw: 0.05,
             cost_color: 0.15,
+            xfa_surcharge: 0.0,
+            attachment_fee: 0.0,
+            color_conversion_fee: 0.0,
+            skip_blank_pages: false,
+            coverage_based: false,
+            coverage_rate_bw: 0.5,
+            coverage_rate_color: 1.5,
+            color_coverage_billing_threshold: 0.0,
+            highlight_cost_above: 0.0,
             show_per_pdf: true,
         }
     }
@@ -34,18 +53,95 @@ impl OutputModule for CostOutput {
                 label: "Cost per B&W page",
                 default: ConfigValue::Float(0.05),
                 description: "Cost in currency units per black & white page",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
             },
             ConfigParam {
                 key: "cost_color",
                 label: "Cost per color page",
                 default: ConfigValue::Float(0.15),
                 description: "Cost in currency units per color page",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "xfa_surcharge",
+                label: "XFA form surcharge",
+                default: ConfigValue::Float(0.0),
+                description: "Flat fee per PDF with an XFA form, which needs special processing",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "attachment_fee",
+                label: "Attachment fee",
+                default: ConfigValue::Float(0.0),
+                description: "Flat fee per PDF carrying embedded file attachments",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "color_conversion_fee",
+                label: "RGB conversion fee",
+                default: ConfigValue::Float(0.0),
+                description: "Fee per billed color page with RGB images, for converting to CMYK",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "skip_blank_pages",
+                label: "Don't bill blank pages",
+                default: ConfigValue::Bool(false),
+                description: "Subtract pages detected as blank from the B&W page count",
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "coverage_based",
+                label: "Coverage-based pricing",
+                default: ConfigValue::Bool(false),
+                description: "Charge by measured ink coverage instead of a flat rate per page",
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "coverage_rate_bw",
+                label: "B&W rate at full coverage",
+                default: ConfigValue::Float(0.5),
+                description: "Cost of a B&W page fully covered with ink, scaled by its coverage",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "coverage_rate_color",
+                label: "Color rate at full coverage",
+                default: ConfigValue::Float(1.5),
+                description: "Cost of a color page fully covered with ink, scaled by its coverage",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "color_coverage_billing_threshold",
+                label: "Minimum color coverage",
+                default: ConfigValue::Float(0.0),
+                description: "Color pages with a smaller share of colored pixels are billed as B&W",
+                min: Some(ConfigValue::Float(0.0)),
+                max: Some(ConfigValue::Float(1.0)),
+                step: Some(0.001),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "highlight_cost_above",
+                label: "Highlight files costing more than",
+                default: ConfigValue::Float(0.0),
+                description: "Mark files costing more than this in the results, 0 to disable",
+                min: Some(ConfigValue::Float(0.0)),
+                ..ConfigParam::default()
             },
             ConfigParam {
                 key: "show_per_pdf",
                 label: "Show per-PDF breakdown",
                 default: ConfigValue::Bool(true),
                 description: "Display costs for each individual PDF file",
+                ..ConfigParam::default()
             },
         ]
     }
@@ -57,6 +153,42 @@ impl OutputModule for CostOutput {
         if let Some(ConfigValue::Float(v)) = config.get_output_value(self.id(), "cost_color") {
             self.cost_color = *v;
         }
+        if let Some(ConfigValue::Float(v)) = config.get_output_value(self.id(), "xfa_surcharge") {
+            self.xfa_surcharge = *v;
+        }
+        if let Some(ConfigValue::Float(v)) = config.get_output_value(self.id(), "attachment_fee") {
+            self.attachment_fee = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "color_conversion_fee")
+        {
+            self.color_conversion_fee = *v;
+        }
+        if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "skip_blank_pages") {
+            self.skip_blank_pages = *v;
+        }
+        if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "coverage_based") {
+            self.coverage_based = *v;
+        }
+        if let Some(ConfigValue::Float(v)) = config.get_output_value(self.id(), "coverage_rate_bw")
+        {
+            self.coverage_rate_bw = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "coverage_rate_color")
+        {
+            self.coverage_rate_color = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "color_coverage_billing_threshold")
+        {
+            self.color_coverage_billing_threshold = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "highlight_cost_above")
+        {
+            self.highlight_cost_above = *v;
+        }
         if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "show_per_pdf") {
             self.show_per_pdf = *v;
         }
@@ -65,77 +197,200 @@ impl OutputModule for CostOutput {
     fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
         let mut total_bw_cost = 0.0f64;
         let mut total_color_cost = 0.0f64;
+        let mut total_surcharges = 0.0f64;
 
         let mut per_pdf = Vec::new();
 
         for result in results {
-            let mut bw = 0usize;
+            let mut bw = None;
+            let mut total_pages = 0usize;
             let mut color = 0usize;
+            let mut surcharges = 0.0f64;
+            let mut blank = 0usize;
+            let mut color_numbers: &[usize] = &[];
+            let mut color_coverage: &[f32] = &[];
+            let mut coverage: Option<(&[f64], usize)> = None;
+            let mut rgb_numbers: &[usize] = &[];
 
             for analysis in &result.results {
-                if let AnalysisResult::ColorAnalysis { bw_pages, color_pages } = analysis {
-                    bw = *bw_pages;
-                    color = *color_pages;
+                match analysis {
+                    AnalysisResult::ColorAnalysis {
+                        bw_pages,
+                        color_pages,
+                        color_page_numbers,
+                        color_page_coverage,
+                        ..
+                    }
+                    | AnalysisResult::ColorAnalysisDetailed {
+                        bw_pages,
+                        color_pages,
+                        color_page_numbers,
+                        color_page_coverage,
+                        ..
+                    } => {
+                        bw = Some(*bw_pages);
+                        color = *color_pages;
+                        color_numbers = color_page_numbers;
+                        color_coverage = color_page_coverage;
+                    }
+                    AnalysisResult::PageCount { total } => {
+                        total_pages = *total;
+                    }
+                    AnalysisResult::InkCoverage {
+                        per_page,
+                        first_page,
+                        ..
+                    } => {
+                        coverage = Some((per_page, *first_page));
+                    }
+                    AnalysisResult::ColorSpaceAnalysis {
+                        rgb_page_numbers, ..
+                    } => {
+                        rgb_numbers = rgb_page_numbers;
+                    }
+                    AnalysisResult::FormAnalysis { has_xfa: true, .. } => {
+                        surcharges += self.xfa_surcharge;
+                    }
+                    AnalysisResult::EmbeddedFilesAnalysis { count, .. } if *count > 0 => {
+                        surcharges += self.attachment_fee;
+                    }
+                    AnalysisResult::BlankPages { blank_count, .. } => {
+                        blank = *blank_count;
+                    }
+                    _ => {}
                 }
             }
 
-            let bw_cost = bw as f64 * self.cost_bw;
-            let color_cost = color as f64 * self.cost_color;
-            let file_total = bw_cost + color_cost;
+            // Pages with only a little color, like a logo, are billed as B&W
+            let billed_color_numbers: Vec<usize> = color_numbers
+                .iter()
+                .zip(color_coverage)
+                .filter(|(_, coverage)| **coverage as f64 >= self.color_coverage_billing_threshold)
+                .map(|(number, _)| *number)
+                .collect();
+            let billed_color = billed_color_numbers.len();
+
+            // Only color pages that are billed as such and hold RGB content need converting
+            let rgb_color = billed_color_numbers
+                .iter()
+                .filter(|number| rgb_numbers.contains(number))
+                .count();
+            surcharges += rgb_color as f64 * self.color_conversion_fee;
+
+            // With color analysis disabled every page is billed as B&W
+            let mut bw = bw.map_or(total_pages, |bw| bw + color - billed_color);
+
+            let (bw_cost, color_cost, mode) = match coverage {
+                // Without coverage data the flat rates apply even in coverage mode
+                Some((per_page, first_page)) if self.coverage_based => {
+                    let mut bw_cost = 0.0;
+                    let mut color_cost = 0.0;
+                    for (index, page_coverage) in per_page.iter().enumerate() {
+                        if billed_color_numbers.contains(&(first_page + index)) {
+                            color_cost += page_coverage * self.coverage_rate_color;
+                        } else {
+                            bw_cost += page_coverage * self.coverage_rate_bw;
+                        }
+                    }
+                    (bw_cost, color_cost, "Coverage")
+                }
+                _ => {
+                    // Blank pages never contain color, so they are taken off the B&W count
+                    if self.skip_blank_pages {
+                        bw = bw.saturating_sub(blank);
+                    }
+                    (
+                        bw as f64 * self.cost_bw,
+                        billed_color as f64 * self.cost_color,
+                        "Flat",
+                    )
+                }
+            };
+            let file_total = bw_cost + color_cost + surcharges;
 
             total_bw_cost += bw_cost;
             total_color_cost += color_cost;
+            total_surcharges += surcharges;
 
             if self.show_per_pdf {
                 per_pdf.push(OutputRow {
                     filename: result.filename.clone(),
+                    path: Some(result.path.clone()),
                     values: vec![
                         ("B&W Cost".to_string(), format!("{:.2}", bw_cost)),
                         ("Color Cost".to_string(), format!("{:.2}", color_cost)),
+                        ("Surcharges".to_string(), format!("{:.2}", surcharges)),
                         ("Total".to_string(), format!("{:.2}", file_total)),
+                        ("Pricing".to_string(), mode.to_string()),
                     ],
+                    highlight: self.highlight_cost_above > 0.0
+                        && file_total > self.highlight_cost_above,
                 });
             }
         }
 
-        let grand_total = total_bw_cost + total_color_cost;
+        let grand_total = total_bw_cost + total_color_cost + total_surcharges;
 
         let totals = vec![
             ("Total B&W Cost".to_string(), format!("{:.2}", total_bw_cost)),
             ("Total Color Cost".to_string(), format!("{:.2}", total_color_cost)),
+            ("Total Surcharges".to_string(), format!("{:.2}", total_surcharges)),
             ("Grand Total".to_string(), format!("{:.2}", grand_total)),
         ];
 
         let mut copyable_text = String::new();
         copyable_text.push_str("=== Cost Calculation ===\n\n");
-        copyable_text.push_str(&format!("Rates: B&W = {:.2}/page, Color = {:.2}/page\n\n",
+        copyable_text.push_str(&format!("Rates: B&W = {:.2}/page, Color = {:.2}/page\n",
             self.cost_bw, self.cost_color));
+        if self.coverage_based {
+            copyable_text.push_str(&format!(
+                "Coverage rates: B&W = {:.2}, Color = {:.2} per fully covered page\n",
+                self.coverage_rate_bw, self.coverage_rate_color
+            ));
+        }
+        if self.color_coverage_billing_threshold > 0.0 {
+            copyable_text.push_str(&format!(
+                "Color pages below {:.1}% color coverage are billed as B&W\n",
+                self.color_coverage_billing_threshold * 100.0
+            ));
+        }
+        copyable_text.push('\n');
 
         if self.show_per_pdf {
             copyable_text.push_str("Per-PDF Breakdown:\n");
             for row in &per_pdf {
                 copyable_text.push_str(&format!(
-                    "  {}: B&W {}, Color {}, Total {}\n",
+                    "  {}: B&W {}, Color {}, Surcharges {}, Total {} ({} pricing)\n",
                     row.filename,
                     row.values[0].1,
                     row.values[1].1,
-                    row.values[2].1
+                    row.values[2].1,
+                    row.values[3].1,
+                    row.values[4].1
                 ));
             }
             copyable_text.push('\n');
         }
 
         copyable_text.push_str(&format!(
-            "Totals: B&W {:.2}, Color {:.2}\nGrand Total: {:.2}\n",
-            total_bw_cost, total_color_cost, grand_total
+            "Totals: B&W {:.2}, Color {:.2}, Surcharges {:.2}\nGrand Total: {:.2}\n",
+            total_bw_cost, total_color_cost, total_surcharges, grand_total
         ));
 
         OutputData {
             title: "Cost Calculation".to_string(),
-            columns: vec!["File".to_string(), "B&W Cost".to_string(), "Color Cost".to_string(), "Total".to_string()],
+            columns: vec![
+                "File".to_string(),
+                "B&W Cost".to_string(),
+                "Color Cost".to_string(),
+                "Surcharges".to_string(),
+                "Total".to_string(),
+                "Pricing".to_string(),
+            ],
             per_pdf,
             totals,
             copyable_text,
+            file_extension: "txt",
         }
     }
 }
@@ -0,0 +1,225 @@
+use crate::analyzer::{AnalysisResult, PdfAnalysisResult};
+use super::{format_size, OutputData, OutputModule, OutputRow};
+
+/// Compares the results with a previously exported analysis, matching files
+/// by path, to catch page counts, color counts or sizes that changed between
+/// two batches.
+#[derive(Default)]
+pub struct DiffOutput {
+    baseline: Vec<PdfAnalysisResult>,
+}
+
+/// The numbers of one file that are compared
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Metrics {
+    pages: Option<u64>,
+    color_pages: Option<u64>,
+    bytes: Option<u64>,
+}
+
+impl Metrics {
+    fn of(result: &PdfAnalysisResult) -> Self {
+        let mut metrics = Self {
+            pages: None,
+            color_pages: None,
+            bytes: None,
+        };
+        for analysis in &result.results {
+            match analysis {
+                AnalysisResult::PageCount { total } => metrics.pages = Some(*total as u64),
+                AnalysisResult::ColorAnalysis { color_pages, .. }
+                | AnalysisResult::ColorAnalysisDetailed { color_pages, .. } => {
+                    metrics.color_pages = Some(*color_pages as u64)
+                }
+                AnalysisResult::FileSize { bytes, .. } => metrics.bytes = Some(*bytes),
+                _ => {}
+            }
+        }
+        metrics
+    }
+
+    /// Only numbers analyzed in both batches count, turning on an analyzer
+    /// is not a change of the file.
+    fn differs(&self, other: &Self) -> bool {
+        let differ =
+            |a: Option<u64>, b: Option<u64>| matches!((a, b), (Some(a), Some(b)) if a != b);
+        differ(self.pages, other.pages)
+            || differ(self.color_pages, other.color_pages)
+            || differ(self.bytes, other.bytes)
+    }
+}
+
+/// Whether a baseline entry describes the same file as a current result.
+/// Files are matched by path, as different folders often hold files of the
+/// same name; by name only when the baseline has no path recorded.
+fn same_file(baseline: &PdfAnalysisResult, result: &PdfAnalysisResult) -> bool {
+    if baseline.path.is_empty() {
+        baseline.filename == result.filename
+    } else {
+        baseline.path == result.path
+    }
+}
+
+/// "12" when unchanged, "12 → 14" when changed, "-" when analyzed in neither batch.
+fn compare_cell(before: Option<u64>, after: Option<u64>) -> String {
+    compare_cell_with(before, after, |value| value.to_string())
+}
+
+fn compare_cell_with(before: Option<u64>, after: Option<u64>, show: fn(u64) -> String) -> String {
+    match (before, after) {
+        (Some(before), Some(after)) if before != after => {
+            format!("{} → {}", show(before), show(after))
+        }
+        (_, Some(value)) | (Some(value), None) => show(value),
+        (None, None) => "-".to_string(),
+    }
+}
+
+impl OutputModule for DiffOutput {
+    fn id(&self) -> &'static str {
+        "diff"
+    }
+
+    fn name(&self) -> &'static str {
+        "Comparison"
+    }
+
+    fn set_baseline(&mut self, baseline: &[PdfAnalysisResult]) {
+        self.baseline = baseline.to_vec();
+    }
+
+    fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
+        let columns = vec![
+            "File".to_string(),
+            "Pages".to_string(),
+            "Color Pages".to_string(),
+            "Size".to_string(),
+            "Status".to_string(),
+        ];
+
+        if self.baseline.is_empty() {
+            return OutputData {
+                title: "Comparison".to_string(),
+                columns,
+                per_pdf: vec![],
+                totals: vec![("Baseline".to_string(), "None loaded".to_string())],
+                copyable_text: "=== Comparison ===\n\nNo saved analysis loaded to compare with.\n"
+                    .to_string(),
+                file_extension: "txt",
+            };
+        }
+
+        let mut per_pdf = Vec::new();
+        let mut changed = 0;
+        let mut added = 0;
+        let mut unchanged = 0;
+
+        // Current files first and in order, so rows line up with the results
+        for result in results {
+            let after = Metrics::of(result);
+            let before = self
+                .baseline
+                .iter()
+                .find(|b| same_file(b, result))
+                .map(Metrics::of);
+
+            let status = match before {
+                None => {
+                    added += 1;
+                    "New"
+                }
+                Some(before) if before.differs(&after) => {
+                    changed += 1;
+                    "Changed"
+                }
+                Some(_) => {
+                    unchanged += 1;
+                    "Unchanged"
+                }
+            };
+            let before = before.unwrap_or(after);
+
+            per_pdf.push(OutputRow {
+                filename: result.filename.clone(),
+                path: Some(result.path.clone()),
+                values: vec![
+                    ("Pages".to_string(), compare_cell(before.pages, after.pages)),
+                    (
+                        "Color Pages".to_string(),
+                        compare_cell(before.color_pages, after.color_pages),
+                    ),
+                    (
+                        "Size".to_string(),
+                        compare_cell_with(before.bytes, after.bytes, format_size),
+                    ),
+                    ("Status".to_string(), status.to_string()),
+                ],
+                highlight: status == "Changed",
+            });
+        }
+
+        let removed: Vec<&PdfAnalysisResult> = self
+            .baseline
+            .iter()
+            .filter(|b| !results.iter().any(|r| same_file(b, r)))
+            .collect();
+        for result in &removed {
+            let before = Metrics::of(result);
+            per_pdf.push(OutputRow {
+                filename: result.filename.clone(),
+                path: None,
+                values: vec![
+                    ("Pages".to_string(), compare_cell(before.pages, None)),
+                    (
+                        "Color Pages".to_string(),
+                        compare_cell(before.color_pages, None),
+                    ),
+                    (
+                        "Size".to_string(),
+                        compare_cell_with(before.bytes, None, format_size),
+                    ),
+                    ("Status".to_string(), "Removed".to_string()),
+                ],
+                highlight: false,
+            });
+        }
+
+        let totals = vec![
+            (
+                "Baseline Files".to_string(),
+                self.baseline.len().to_string(),
+            ),
+            ("Changed".to_string(), changed.to_string()),
+            ("Unchanged".to_string(), unchanged.to_string()),
+            ("New".to_string(), added.to_string()),
+            ("Removed".to_string(), removed.len().to_string()),
+        ];
+
+        let mut copyable_text = String::from("=== Comparison ===\n\n");
+        for row in per_pdf.iter().filter(|row| row.values[3].1 != "Unchanged") {
+            copyable_text.push_str(&format!(
+                "  {}: pages {}, color pages {}, size {} ({})\n",
+                row.filename, row.values[0].1, row.values[1].1, row.values[2].1, row.values[3].1
+            ));
+        }
+        if changed + added + removed.len() > 0 {
+            copyable_text.push('\n');
+        }
+        copyable_text.push_str(&format!(
+            "Changed: {}, Unchanged: {}, New: {}, Removed: {}\n",
+            changed,
+            unchanged,
+            added,
+            removed.len()
+        ));
+
+        OutputData {
+            title: "Comparison".to_string(),
+            columns,
+            per_pdf,
+            totals,
+            copyable_text,
+            file_extension: "txt",
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::analyzer::{AnalysisResult, PdfAnalysisResult};
+use super::{format_size, OutputData, OutputModule, OutputRow};
+
+/// Groups of files with identical bytes, from the `FileHash` results, and
+/// the space freed by keeping one file of each group.
+#[derive(Default)]
+pub struct DuplicatesOutput;
+
+/// A file and its size in bytes, if known
+type SizedFile<'a> = (&'a PdfAnalysisResult, Option<u64>);
+
+impl OutputModule for DuplicatesOutput {
+    fn id(&self) -> &'static str {
+        "duplicates"
+    }
+
+    fn name(&self) -> &'static str {
+        "Duplicate Files"
+    }
+
+    fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
+        // Files by hash, groups in the order their first file appears
+        let mut groups: Vec<(&str, Vec<SizedFile>)> = Vec::new();
+        for result in results {
+            let mut hash = None;
+            let mut bytes = None;
+            for analysis in &result.results {
+                match analysis {
+                    AnalysisResult::FileHash { sha256 } => hash = Some(sha256.as_str()),
+                    AnalysisResult::FileSize { bytes: size, .. } => bytes = Some(*size),
+                    _ => {}
+                }
+            }
+            let Some(hash) = hash else {
+                continue;
+            };
+            match groups.iter_mut().find(|(h, _)| *h == hash) {
+                Some((_, files)) => files.push((result, bytes)),
+                None => groups.push((hash, vec![(result, bytes)])),
+            }
+        }
+        groups.retain(|(_, files)| files.len() > 1);
+
+        let mut per_pdf = Vec::new();
+        let mut redundant = 0usize;
+        let mut reclaimable = 0u64;
+        let mut copyable_text = String::from("=== Duplicate Files ===\n\n");
+
+        for (number, (hash, files)) in groups.iter().enumerate() {
+            let (original, _) = files[0];
+            copyable_text.push_str(&format!(
+                "Group {} (SHA-256 {}…):\n",
+                number + 1,
+                &hash[..hash.len().min(12)]
+            ));
+            for (index, (result, bytes)) in files.iter().enumerate() {
+                let size = bytes.map_or("—".to_string(), format_size);
+                // The first file of a group is the one to keep
+                let copy_of = if index == 0 {
+                    "—".to_string()
+                } else {
+                    redundant += 1;
+                    reclaimable += bytes.unwrap_or(0);
+                    original.filename.clone()
+                };
+                per_pdf.push(OutputRow {
+                    filename: result.filename.clone(),
+                    path: Some(result.path.clone()),
+                    values: vec![
+                        ("Group".to_string(), (number + 1).to_string()),
+                        ("Copy Of".to_string(), copy_of),
+                        ("Size".to_string(), size.clone()),
+                        ("Path".to_string(), result.path.clone()),
+                    ],
+                    highlight: false,
+                });
+                copyable_text.push_str(&format!("  {} ({})\n", result.path, size));
+            }
+            copyable_text.push('\n');
+        }
+
+        if groups.is_empty() {
+            copyable_text.push_str("No identical files found.\n");
+        } else {
+            copyable_text.push_str(&format!(
+                "{} group(s), {} redundant copies, {} reclaimable\n",
+                groups.len(),
+                redundant,
+                format_size(reclaimable)
+            ));
+        }
+
+        let totals = vec![
+            ("Duplicate Groups".to_string(), groups.len().to_string()),
+            ("Redundant Copies".to_string(), redundant.to_string()),
+            ("Reclaimable Space".to_string(), format_size(reclaimable)),
+        ];
+
+        OutputData {
+            title: "Duplicate Files".to_string(),
+            columns: vec![
+                "File".to_string(),
+                "Group".to_string(),
+                "Copy Of".to_string(),
+                "Size".to_string(),
+                "Path".to_string(),
+            ],
+            per_pdf,
+            totals,
+            copyable_text,
+            file_extension: "txt",
+        }
+    }
+}
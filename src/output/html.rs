@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use base64::Engine;
+use image::{ImageFormat, RgbaImage};
+
+use crate::analyzer::{AnalysisResult, PdfAnalysisResult};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::error::Result;
+use super::cost::CostOutput;
+use super::summary::SummaryOutput;
+use super::{OutputData, OutputModule};
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }
+th { background: #f0f0f0; }
+tr:nth-child(even) td { background: #fafafa; }
+.chart { max-width: 800px; }
+.bar-row { display: flex; align-items: center; margin: 3px 0; }
+.bar-label { flex: 0 0 220px; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+.bar-track { flex: 1; display: flex; }
+.bar { background: #4a7ebb; height: 1.1em; min-width: 1px; }
+.bar-value { margin-left: 6px; }
+.thumbnails { display: flex; flex-wrap: wrap; gap: 16px; }
+.thumbnails figure { margin: 0; width: 160px; text-align: center; }
+.thumbnails img { max-width: 150px; max-height: 200px; border: 1px solid #ccc; }
+.thumbnails figcaption { font-size: 0.85em; overflow-wrap: anywhere; }
+.notice { background: #fff3cd; border: 1px solid #e0c36c; padding: 6px 10px; }
+";
+
+/// Self-contained HTML report with the summary and cost tables and a page
+/// count chart. Uses the summary and cost outputs so the numbers match them.
+pub struct HtmlOutput {
+    config: Config,
+    embed_thumbnails: bool,
+    /// PNG data URIs of the first pages by PDF path
+    thumbnails: HashMap<String, String>,
+    cancelled: bool,
+}
+
+impl Default for HtmlOutput {
+    fn default() -> Self {
+        Self {
+            config: Config::default(),
+            embed_thumbnails: true,
+            thumbnails: HashMap::new(),
+            cancelled: false,
+        }
+    }
+}
+
+/// Encode an image as a PNG data URI for embedding in HTML.
+pub fn png_data_uri(image: &RgbaImage) -> Option<String> {
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, ImageFormat::Png).ok()?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png.into_inner());
+    Some(format!("data:image/png;base64,{}", encoded))
+}
+
+impl HtmlOutput {
+    /// Write the report to a file that opens directly in a browser.
+    pub fn export_to_html(&self, results: &[PdfAnalysisResult], path: &Path) -> Result<()> {
+        fs::write(path, self.render(results))?;
+        Ok(())
+    }
+
+    fn render(&self, results: &[PdfAnalysisResult]) -> String {
+        let mut summary = SummaryOutput::default();
+        summary.apply_config(&self.config);
+        let mut cost = CostOutput::default();
+        cost.apply_config(&self.config);
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>PDF Analysis Report</title>\n");
+        html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+        html.push_str("<h1>PDF Analysis Report</h1>\n");
+        if self.cancelled {
+            html.push_str(&format!(
+                "<p class=\"notice\">The analysis was cancelled after {} files; files not analyzed yet are missing.</p>\n",
+                results.len()
+            ));
+        }
+
+        for output in [summary.generate(results), cost.generate(results)] {
+            html.push_str(&format!("<h2>{}</h2>\n", escape(&output.title)));
+            html.push_str(&totals_table(&output));
+            if !output.per_pdf.is_empty() {
+                html.push_str(&per_pdf_table(&output));
+            }
+        }
+
+        html.push_str("<h2>Pages per File</h2>\n");
+        html.push_str(&page_chart(results));
+
+        if self.embed_thumbnails && !self.thumbnails.is_empty() {
+            html.push_str("<h2>First Pages</h2>\n");
+            html.push_str(&self.thumbnail_gallery(results));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn thumbnail_gallery(&self, results: &[PdfAnalysisResult]) -> String {
+        let mut html = String::from("<div class=\"thumbnails\">\n");
+        for result in results {
+            let Some(uri) = self.thumbnails.get(&result.path) else {
+                continue;
+            };
+            html.push_str(&format!(
+                "<figure><img src=\"{}\" alt=\"First page of {1}\"><figcaption>{1}</figcaption></figure>\n",
+                uri,
+                escape(&result.filename)
+            ));
+        }
+        html.push_str("</div>\n");
+        html
+    }
+}
+
+impl OutputModule for HtmlOutput {
+    fn id(&self) -> &'static str {
+        "html"
+    }
+
+    fn name(&self) -> &'static str {
+        "HTML Report"
+    }
+
+    fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
+        OutputData {
+            title: "HTML Report".to_string(),
+            columns: vec![],
+            per_pdf: vec![],
+            totals: vec![("Files".to_string(), results.len().to_string())],
+            copyable_text: self.render(results),
+            file_extension: "html",
+        }
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![ConfigParam {
+            key: "embed_thumbnails",
+            label: "Embed thumbnails",
+            default: ConfigValue::Bool(true),
+            description: "Include a picture of each PDF's first page in the report",
+            ..ConfigParam::default()
+        }]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        self.config = config.clone();
+        if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "embed_thumbnails") {
+            self.embed_thumbnails = *v;
+        }
+    }
+
+    fn set_thumbnails(&mut self, thumbnails: &HashMap<String, String>) {
+        self.thumbnails = thumbnails.clone();
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = cancelled;
+    }
+}
+
+fn totals_table(output: &OutputData) -> String {
+    let mut html = String::from("<table>\n");
+    for (label, value) in &output.totals {
+        html.push_str(&format!(
+            "<tr><th>{}</th><td>{}</td></tr>\n",
+            escape(label),
+            escape(value)
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn per_pdf_table(output: &OutputData) -> String {
+    let mut html = String::from("<table>\n<tr>");
+    for column in &output.columns {
+        html.push_str(&format!("<th>{}</th>", escape(column)));
+    }
+    html.push_str("</tr>\n");
+
+    for row in &output.per_pdf {
+        html.push_str(&format!("<tr><td>{}</td>", escape(&row.filename)));
+        for (_, value) in &row.values {
+            html.push_str(&format!("<td>{}</td>", escape(value)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Horizontal bars scaled to the longest file, drawn with flexbox.
+fn page_chart(results: &[PdfAnalysisResult]) -> String {
+    let pages: Vec<(&str, usize)> = results
+        .iter()
+        .map(|result| {
+            let total = result
+                .results
+                .iter()
+                .find_map(|r| match r {
+                    AnalysisResult::PageCount { total } => Some(*total),
+                    _ => None,
+                })
+                .unwrap_or(0);
+            (result.filename.as_str(), total)
+        })
+        .collect();
+    // At least 1 so a file list with only empty PDFs doesn't divide by zero
+    let max = pages.iter().map(|(_, total)| *total).max().unwrap_or(0);
+    let max = max.max(1);
+
+    let mut html = String::from("<div class=\"chart\">\n");
+    for (filename, total) in pages {
+        let percent = total as f64 / max as f64 * 100.0;
+        html.push_str(&format!(
+            "<div class=\"bar-row\"><span class=\"bar-label\" title=\"{0}\">{0}</span>\
+             <span class=\"bar-track\"><span class=\"bar\" style=\"width: {1:.1}%\"></span>\
+             <span class=\"bar-value\">{2}</span></span></div>\n",
+            escape(filename),
+            percent,
+            total
+        ));
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use crate::analyzer::PdfAnalysisResult;
+use crate::config::Config;
+use crate::error::Result;
+use super::cost::CostOutput;
+use super::summary::SummaryOutput;
+use super::{leading_number, OutputData, OutputModule};
+
+/// GitHub Flavored Markdown report with the summary and cost tables, for
+/// pasting into wikis. Uses the summary and cost outputs so the numbers
+/// match them.
+#[derive(Default)]
+pub struct MarkdownOutput {
+    config: Config,
+    cancelled: bool,
+}
+
+impl MarkdownOutput {
+    pub fn export_to_markdown(&self, results: &[PdfAnalysisResult], path: &Path) -> Result<()> {
+        fs::write(path, self.render(results))?;
+        Ok(())
+    }
+
+    fn render(&self, results: &[PdfAnalysisResult]) -> String {
+        let mut summary = SummaryOutput::default();
+        summary.apply_config(&self.config);
+        let mut cost = CostOutput::default();
+        cost.apply_config(&self.config);
+
+        let mut markdown = front_matter(results, self.cancelled);
+        markdown.push_str("\n# PDF Analysis Report\n");
+        if self.cancelled {
+            markdown.push_str(&format!(
+                "\n> The analysis was cancelled after {} files; files not analyzed yet are missing.\n",
+                results.len()
+            ));
+        }
+
+        for output in [summary.generate(results), cost.generate(results)] {
+            markdown.push_str(&format!("\n## {}\n\n", output.title));
+            if !output.per_pdf.is_empty() {
+                markdown.push_str(&table(&output));
+                markdown.push('\n');
+            }
+            for (label, value) in &output.totals {
+                markdown.push_str(&format!("- **{}:** {}\n", label, value));
+            }
+        }
+        markdown
+    }
+}
+
+impl OutputModule for MarkdownOutput {
+    fn id(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn name(&self) -> &'static str {
+        "Markdown Report"
+    }
+
+    fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
+        OutputData {
+            title: "Markdown Report".to_string(),
+            columns: vec![],
+            per_pdf: vec![],
+            totals: vec![("Files".to_string(), results.len().to_string())],
+            copyable_text: self.render(results),
+            file_extension: "md",
+        }
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        self.config = config.clone();
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = cancelled;
+    }
+}
+
+/// YAML front matter with the generation time and the analyzed files.
+fn front_matter(results: &[PdfAnalysisResult], cancelled: bool) -> String {
+    let mut yaml = String::from("---\n");
+    yaml.push_str(&format!(
+        "generated: {}\n",
+        chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+    ));
+    if cancelled {
+        yaml.push_str("cancelled: true\n");
+    }
+    yaml.push_str("files:\n");
+    for result in results {
+        let quoted = result.filename.replace('\\', "\\\\").replace('"', "\\\"");
+        yaml.push_str(&format!("  - \"{}\"\n", quoted));
+    }
+    yaml.push_str("---\n");
+    yaml
+}
+
+fn table(output: &OutputData) -> String {
+    let mut markdown = row(output.columns.iter().map(String::as_str));
+
+    // File names stay left aligned, columns holding only numbers are right aligned
+    let value_alignments = (1..output.columns.len()).map(|column| {
+        if is_numeric_column(output, column - 1) {
+            "---:"
+        } else {
+            "---"
+        }
+    });
+    markdown.push_str(&row(std::iter::once("---").chain(value_alignments)));
+
+    for pdf in &output.per_pdf {
+        let values = pdf.values.iter().map(|(_, value)| value.as_str());
+        markdown.push_str(&row(std::iter::once(pdf.filename.as_str()).chain(values)));
+    }
+    markdown
+}
+
+fn row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells: Vec<String> = cells.map(cell).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Whether every value of a column is a number or the "—" placeholder.
+fn is_numeric_column(output: &OutputData, column: usize) -> bool {
+    let mut values = output
+        .per_pdf
+        .iter()
+        .filter_map(|pdf| pdf.values.get(column).map(|(_, value)| value.as_str()))
+        .filter(|value| *value != "—")
+        .peekable();
+    values.peek().is_some() && values.all(|value| leading_number(value).is_some())
+}
+
+/// Escape a table cell so pipes don't end the cell early and line breaks
+/// don't end the row.
+fn cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
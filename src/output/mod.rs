@@ -1,13 +1,25 @@
+use std::collections::HashMap;
+
 use crate::analyzer::PdfAnalysisResult;
-use crate::config::{Config, ConfigParam, ConfigValue};
+use crate::config::{Config, ConfigParam};
 
 pub mod summary;
 pub mod cost;
+pub mod html;
+pub mod markdown;
+pub mod diff;
+pub mod duplicates;
+pub mod print_time;
 
 #[derive(Debug, Clone)]
 pub struct OutputRow {
     pub filename: String,
+    /// Path of the analyzed file the row describes, `None` for files that
+    /// aren't in the results, e.g. removed since a compared batch
+    pub path: Option<String>,
     pub values: Vec<(String, String)>,
+    /// Shown in the warning color, e.g. for an unusually expensive file
+    pub highlight: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,6 +29,73 @@ pub struct OutputData {
     pub per_pdf: Vec<OutputRow>,
     pub totals: Vec<(String, String)>,
     pub copyable_text: String,
+    /// Extension for saving `copyable_text` to a file, e.g. "txt"
+    pub file_extension: &'static str,
+}
+
+/// Format a byte count with binary units, e.g. "2.4 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Shorten a string to at most `max_chars` characters, marking the cut with "…".
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Order two table cells, comparing them as numbers when both start with one
+/// (ignoring a currency sign or similar prefix), otherwise as text.
+/// Cells with a number sort before cells without.
+pub fn compare_values(a: &str, b: &str) -> std::cmp::Ordering {
+    match (leading_number(a), leading_number(b)) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// First number in a cell, e.g. 12.5 for "$12.50" and 3 for "3 (1, 4, 7)".
+/// Sizes like "2.4 MB" are converted to bytes so they compare across units.
+fn leading_number(value: &str) -> Option<f64> {
+    let start = value.find(|c: char| c.is_ascii_digit())?;
+    // Only skip a short prefix such as "$" or "~", not words
+    if value[..start].chars().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+    let negative = value[..start].ends_with('-');
+
+    let rest = &value[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let number: f64 = rest[..end].parse().ok()?;
+
+    let multiplier = match rest[end..].split_whitespace().next() {
+        Some("KB") => 1024.0,
+        Some("MB") => 1024.0 * 1024.0,
+        Some("GB") => 1024.0 * 1024.0 * 1024.0,
+        Some("TB") => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => 1.0,
+    };
+    Some(if negative { -number } else { number } * multiplier)
 }
 
 pub trait OutputModule: Send + Sync {
@@ -27,15 +106,28 @@ pub trait OutputModule: Send + Sync {
         vec![]
     }
     fn apply_config(&mut self, _config: &Config) {}
+    /// First-page thumbnails as PNG data URIs by PDF path, for outputs that
+    /// embed them.
+    fn set_thumbnails(&mut self, _thumbnails: &HashMap<String, String>) {}
+    /// Results of an earlier analysis, for outputs that compare against it.
+    fn set_baseline(&mut self, _baseline: &[PdfAnalysisResult]) {}
+    /// Whether the analysis was cancelled, for outputs that note the results
+    /// are partial.
+    fn set_cancelled(&mut self, _cancelled: bool) {}
 }
 
 pub struct OutputRegistry {
     outputs: Vec<Box<dyn OutputModule>>,
+    /// Enabled flags by output id from the config, missing ones are enabled
+    enabled: HashMap<String, bool>,
 }
 
 impl OutputRegistry {
     pub fn new() -> Self {
-        Self { outputs: vec![] }
+        Self {
+            outputs: vec![],
+            enabled: HashMap::new(),
+        }
     }
 
     pub fn register(&mut self, output: Box<dyn OutputModule>) {
@@ -46,22 +138,51 @@ impl OutputRegistry {
         &self.outputs
     }
 
+    /// Outputs not switched off in the config, in registration order.
+    pub fn enabled_outputs(&self) -> impl Iterator<Item = &Box<dyn OutputModule>> {
+        self.outputs
+            .iter()
+            .filter(|o| self.enabled.get(o.id()).copied().unwrap_or(true))
+    }
+
     pub fn apply_config(&mut self, config: &Config) {
+        self.enabled = config.enabled.clone();
         for output in &mut self.outputs {
             output.apply_config(config);
         }
     }
 
+    /// Settings of every output, including those without parameters so
+    /// they can still be switched on and off.
     pub fn all_config_params(&self) -> Vec<(&'static str, &'static str, Vec<ConfigParam>)> {
         self.outputs
             .iter()
             .map(|o| (o.id(), o.name(), o.config_params()))
-            .filter(|(_, _, params)| !params.is_empty())
             .collect()
     }
 
+    pub fn set_thumbnails(&mut self, thumbnails: &HashMap<String, String>) {
+        for output in &mut self.outputs {
+            output.set_thumbnails(thumbnails);
+        }
+    }
+
+    pub fn set_baseline(&mut self, baseline: &[PdfAnalysisResult]) {
+        for output in &mut self.outputs {
+            output.set_baseline(baseline);
+        }
+    }
+
+    pub fn set_cancelled(&mut self, cancelled: bool) {
+        for output in &mut self.outputs {
+            output.set_cancelled(cancelled);
+        }
+    }
+
     pub fn generate_all(&self, results: &[PdfAnalysisResult]) -> Vec<OutputData> {
-        self.outputs.iter().map(|o| o.generate(results)).collect()
+        self.enabled_outputs()
+            .map(|o| o.generate(results))
+            .collect()
     }
 }
 
@@ -70,6 +191,11 @@ impl Default for OutputRegistry {
         let mut registry = Self::new();
         registry.register(Box::new(summary::SummaryOutput::default()));
         registry.register(Box::new(cost::CostOutput::default()));
+        registry.register(Box::new(print_time::PrintTimeOutput::default()));
+        registry.register(Box::new(html::HtmlOutput::default()));
+        registry.register(Box::new(markdown::MarkdownOutput::default()));
+        registry.register(Box::new(diff::DiffOutput::default()));
+        registry.register(Box::new(duplicates::DuplicatesOutput));
         registry
     }
 }
@@ -0,0 +1,187 @@
+use crate::analyzer::{AnalysisResult, PdfAnalysisResult};
+use crate::config::{Config, ConfigParam, ConfigValue};
+use super::{OutputData, OutputModule, OutputRow};
+
+/// Estimated printing duration from the B&W and color page counts and the
+/// printer's speed for each.
+pub struct PrintTimeOutput {
+    bw_pages_per_minute: f64,
+    color_pages_per_minute: f64,
+    show_per_pdf: bool,
+}
+
+impl Default for PrintTimeOutput {
+    fn default() -> Self {
+        Self {
+            bw_pages_per_minute: 40.0,
+            color_pages_per_minute: 20.0,
+            show_per_pdf: true,
+        }
+    }
+}
+
+/// Format seconds as HH:MM:SS, rounded to the nearest second.
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        total / 60 % 60,
+        total % 60
+    )
+}
+
+impl PrintTimeOutput {
+    /// Seconds to print `pages`, 0 for a speed of 0 so a misconfigured rate
+    /// doesn't yield an infinite estimate.
+    fn seconds(pages: usize, pages_per_minute: f64) -> f64 {
+        if pages_per_minute > 0.0 {
+            pages as f64 / pages_per_minute * 60.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl OutputModule for PrintTimeOutput {
+    fn id(&self) -> &'static str {
+        "print_time"
+    }
+
+    fn name(&self) -> &'static str {
+        "Print Time"
+    }
+
+    fn config_params(&self) -> Vec<ConfigParam> {
+        vec![
+            ConfigParam {
+                key: "bw_pages_per_minute",
+                label: "B&W pages per minute",
+                default: ConfigValue::Float(40.0),
+                description: "Printer speed for black & white pages",
+                min: Some(ConfigValue::Float(0.0)),
+                step: Some(1.0),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "color_pages_per_minute",
+                label: "Color pages per minute",
+                default: ConfigValue::Float(20.0),
+                description: "Printer speed for color pages",
+                min: Some(ConfigValue::Float(0.0)),
+                step: Some(1.0),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "show_per_pdf",
+                label: "Show per-PDF breakdown",
+                default: ConfigValue::Bool(true),
+                description: "Display the print time of each individual PDF file",
+                ..ConfigParam::default()
+            },
+        ]
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "bw_pages_per_minute")
+        {
+            self.bw_pages_per_minute = *v;
+        }
+        if let Some(ConfigValue::Float(v)) =
+            config.get_output_value(self.id(), "color_pages_per_minute")
+        {
+            self.color_pages_per_minute = *v;
+        }
+        if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "show_per_pdf") {
+            self.show_per_pdf = *v;
+        }
+    }
+
+    fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
+        let mut total_seconds = 0.0f64;
+        let mut per_pdf = Vec::new();
+
+        for result in results {
+            let mut total_pages = 0usize;
+            let mut color_counts = None;
+
+            for analysis in &result.results {
+                match analysis {
+                    AnalysisResult::PageCount { total } => total_pages = *total,
+                    AnalysisResult::ColorAnalysis {
+                        bw_pages,
+                        color_pages,
+                        ..
+                    }
+                    | AnalysisResult::ColorAnalysisDetailed {
+                        bw_pages,
+                        color_pages,
+                        ..
+                    } => color_counts = Some((*bw_pages, *color_pages)),
+                    _ => {}
+                }
+            }
+
+            // With color analysis disabled every page is printed at the B&W speed
+            let (bw, color) = color_counts.unwrap_or((total_pages, 0));
+            let seconds = Self::seconds(bw, self.bw_pages_per_minute)
+                + Self::seconds(color, self.color_pages_per_minute);
+            total_seconds += seconds;
+
+            if self.show_per_pdf {
+                per_pdf.push(OutputRow {
+                    filename: result.filename.clone(),
+                    path: Some(result.path.clone()),
+                    values: vec![
+                        ("B&W Pages".to_string(), bw.to_string()),
+                        ("Color Pages".to_string(), color.to_string()),
+                        ("Print Time".to_string(), format_duration(seconds)),
+                    ],
+                    highlight: false,
+                });
+            }
+        }
+
+        let totals = vec![(
+            "Total Print Time".to_string(),
+            format_duration(total_seconds),
+        )];
+
+        let mut copyable_text = String::new();
+        copyable_text.push_str("=== Print Time ===\n\n");
+        copyable_text.push_str(&format!(
+            "Speed: B&W = {:.0} pages/min, Color = {:.0} pages/min\n\n",
+            self.bw_pages_per_minute, self.color_pages_per_minute
+        ));
+        if self.show_per_pdf {
+            copyable_text.push_str("Per-PDF Breakdown:\n");
+            for row in &per_pdf {
+                copyable_text.push_str(&format!(
+                    "  {}: {} ({} B&W, {} color pages)\n",
+                    row.filename, row.values[2].1, row.values[0].1, row.values[1].1
+                ));
+            }
+            copyable_text.push('\n');
+        }
+        copyable_text.push_str(&format!(
+            "Total: {} (about {:.0} min)\n",
+            format_duration(total_seconds),
+            total_seconds / 60.0
+        ));
+
+        OutputData {
+            title: "Print Time".to_string(),
+            columns: vec![
+                "File".to_string(),
+                "B&W Pages".to_string(),
+                "Color Pages".to_string(),
+                "Print Time".to_string(),
+            ],
+            per_pdf,
+            totals,
+            copyable_text,
+            file_extension: "txt",
+        }
+    }
+}
@@ -1,14 +1,22 @@
+use crate::analyzer::hyperlink_analysis::MAX_UNIQUE_URLS;
+use crate::analyzer::pdf_version::parse_version;
 use crate::analyzer::{AnalysisResult, PdfAnalysisResult};
 use crate::config::{Config, ConfigParam, ConfigValue};
-use super::{OutputData, OutputModule, OutputRow};
+use super::{format_size, truncate, OutputData, OutputModule, OutputRow};
 
 pub struct SummaryOutput {
     show_per_pdf: bool,
+    warn_below_version: String,
+    warn_no_outline_pages: i64,
 }
 
 impl Default for SummaryOutput {
     fn default() -> Self {
-        Self { show_per_pdf: true }
+        Self {
+            show_per_pdf: true,
+            warn_below_version: "1.4".to_string(),
+            warn_no_outline_pages: 0,
+        }
     }
 }
 
@@ -22,57 +30,462 @@ impl OutputModule for SummaryOutput {
     }
 
     fn config_params(&self) -> Vec<ConfigParam> {
-        vec![ConfigParam {
-            key: "show_per_pdf",
-            label: "Show per-PDF breakdown",
-            default: ConfigValue::Bool(true),
-            description: "Display page counts for each individual PDF file",
-        }]
+        vec![
+            ConfigParam {
+                key: "show_per_pdf",
+                label: "Show per-PDF breakdown",
+                default: ConfigValue::Bool(true),
+                description: "Display page counts for each individual PDF file",
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "warn_below_version",
+                label: "Warn below PDF version",
+                default: ConfigValue::String("1.4".to_string()),
+                description: "PDFs older than this version are marked with ⚠",
+                options: Some(vec![
+                    "1.0", "1.1", "1.2", "1.3", "1.4", "1.5", "1.6", "1.7", "2.0",
+                ]),
+                ..ConfigParam::default()
+            },
+            ConfigParam {
+                key: "warn_no_outline_pages",
+                label: "Warn without bookmarks above (pages)",
+                default: ConfigValue::Int(0),
+                description:
+                    "Documents with more pages and no bookmarks are marked with ⚠, 0 to disable",
+                min: Some(ConfigValue::Int(0)),
+                step: Some(1.0),
+                ..ConfigParam::default()
+            },
+        ]
     }
 
     fn apply_config(&mut self, config: &Config) {
         if let Some(ConfigValue::Bool(v)) = config.get_output_value(self.id(), "show_per_pdf") {
             self.show_per_pdf = *v;
         }
+        if let Some(ConfigValue::String(v)) =
+            config.get_output_value(self.id(), "warn_below_version")
+        {
+            self.warn_below_version = v.clone();
+        }
+        if let Some(ConfigValue::Int(v)) =
+            config.get_output_value(self.id(), "warn_no_outline_pages")
+        {
+            self.warn_no_outline_pages = *v;
+        }
     }
 
     fn generate(&self, results: &[PdfAnalysisResult]) -> OutputData {
         let mut total_pages = 0usize;
         let mut total_bw = 0usize;
         let mut total_color = 0usize;
+        let mut total_low_dpi = 0usize;
+        let mut total_images = 0usize;
+        let mut lowest_dpi: Option<f32> = None;
+        let mut compressions: Vec<String> = Vec::new();
+        let mut total_chars = 0usize;
+        let mut total_words = 0usize;
+        let mut total_bytes = 0u64;
+
+        let min_version = parse_version(&self.warn_below_version);
 
         let mut per_pdf = Vec::new();
+        let mut per_pdf_color_pages = Vec::new();
+        let mut per_pdf_urls = Vec::new();
+        let mut per_pdf_layers = Vec::new();
+        let mut per_pdf_spot_colors = Vec::new();
 
         for result in results {
             let mut pages = 0usize;
             let mut bw = 0usize;
             let mut color = 0usize;
+            let mut low_dpi = 0usize;
+            let mut page_sizes = String::new();
+            let mut color_numbers: &[usize] = &[];
+            let mut color_coverage = (0.0f32, 0.0f32);
+            let mut security = "None".to_string();
+            let mut form_fields = "—".to_string();
+            let mut attachments = "0".to_string();
+            let mut pdf_version = "—".to_string();
+            let mut blank_pages = "0".to_string();
+            let mut has_text = "—".to_string();
+            let mut links = "0".to_string();
+            let mut ink_coverage = "—".to_string();
+            let mut layers = "—".to_string();
+            let mut names: &[String] = &[];
+            let mut color_space = "—".to_string();
+            let mut spot_colors: &[String] = &[];
+            let mut transparency = "—".to_string();
+            let mut overprint = "—".to_string();
+            let mut rotated_pages = "0".to_string();
+            let mut orientation = "—".to_string();
+            let mut file_size = "—".to_string();
+            let mut annotations = "—".to_string();
+            let mut outline = None;
+            let mut urls: &[String] = &[];
 
             for analysis in &result.results {
                 match analysis {
                     AnalysisResult::PageCount { total } => {
                         pages = *total;
                     }
-                    AnalysisResult::ColorAnalysis { bw_pages, color_pages } => {
+                    AnalysisResult::ColorAnalysis {
+                        bw_pages,
+                        color_pages,
+                        color_page_numbers,
+                        avg_color_coverage,
+                        max_color_coverage,
+                        ..
+                    }
+                    | AnalysisResult::ColorAnalysisDetailed {
+                        bw_pages,
+                        color_pages,
+                        color_page_numbers,
+                        avg_color_coverage,
+                        max_color_coverage,
+                        ..
+                    } => {
                         bw = *bw_pages;
                         color = *color_pages;
+                        color_numbers = color_page_numbers;
+                        color_coverage = (*avg_color_coverage, *max_color_coverage);
+                    }
+                    AnalysisResult::ImageAnalysis {
+                        total_images: images,
+                        below_threshold,
+                        min_dpi,
+                        compression_types,
+                        ..
+                    } => {
+                        low_dpi = *below_threshold;
+                        total_images += images;
+                        if let Some(dpi) = min_dpi {
+                            lowest_dpi = Some(lowest_dpi.map_or(*dpi, |m| m.min(*dpi)));
+                        }
+                        for compression in compression_types {
+                            if !compressions.contains(compression) {
+                                compressions.push(compression.clone());
+                            }
+                        }
+                    }
+                    AnalysisResult::PageSizeAnalysis {
+                        sizes,
+                        named_sizes,
+                        has_mixed_sizes,
+                        has_mixed_orientations,
+                        dominant_size,
+                    } => {
+                        page_sizes = named_sizes
+                            .iter()
+                            .map(|(name, count)| format!("{} ×{}", name, count))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if *has_mixed_sizes {
+                            page_sizes = format!("⚠ {} sizes: {}", sizes.len(), page_sizes);
+                            if let Some((w, h)) = dominant_size {
+                                page_sizes.push_str(&format!(" (mostly {}×{} pt)", w, h));
+                            }
+                        }
+                        if *has_mixed_orientations {
+                            page_sizes.push_str(", mixed orientation");
+                        }
+                    }
+                    AnalysisResult::SecurityAnalysis {
+                        is_encrypted,
+                        requires_password,
+                        permissions,
+                    } => {
+                        security = if *requires_password {
+                            "Password required".to_string()
+                        } else if *is_encrypted {
+                            match permissions {
+                                Some(bits) => format!("Encrypted ({:#x})", bits),
+                                None => "Encrypted".to_string(),
+                            }
+                        } else {
+                            "None".to_string()
+                        };
+                    }
+                    AnalysisResult::FormAnalysis {
+                        has_acroform,
+                        field_count,
+                        has_xfa,
+                        required_fields,
+                    } if *has_acroform || *field_count > 0 => {
+                        form_fields = format!("{} ({} required)", field_count, required_fields);
+                        if *has_xfa {
+                            form_fields.push_str(" XFA");
+                        }
+                    }
+                    AnalysisResult::EmbeddedFilesAnalysis {
+                        count,
+                        total_size_bytes,
+                        filenames,
+                    } if *count > 0 => {
+                        // Keep long attachment names from blowing up the grid layout
+                        let names: Vec<String> =
+                            filenames.iter().map(|name| truncate(name, 64)).collect();
+                        attachments = format!(
+                            "{} ({}): {}",
+                            count,
+                            format_size(*total_size_bytes),
+                            names.join(", ")
+                        );
+                    }
+                    AnalysisResult::PdfVersion { version } => {
+                        pdf_version = match (parse_version(version), min_version) {
+                            (Some(v), Some(min)) if v < min => format!("⚠ {}", version),
+                            _ => version.clone(),
+                        };
+                    }
+                    AnalysisResult::BlankPages {
+                        blank_count,
+                        blank_page_indices,
+                    } => {
+                        blank_pages = blank_count.to_string();
+                        if !blank_page_indices.is_empty() {
+                            let numbers: Vec<String> =
+                                blank_page_indices.iter().map(|n| n.to_string()).collect();
+                            blank_pages.push_str(&format!(" (p. {})", numbers.join(", ")));
+                        }
+                    }
+                    AnalysisResult::TextContent {
+                        total_chars: chars,
+                        total_words: words,
+                        has_text_layer,
+                        scanned_pages,
+                    } => {
+                        total_chars += chars;
+                        total_words += words;
+                        has_text = if !has_text_layer {
+                            "No".to_string()
+                        } else if *scanned_pages > 0 {
+                            format!("Yes ({} scanned)", scanned_pages)
+                        } else {
+                            "Yes".to_string()
+                        };
+                    }
+                    AnalysisResult::HyperlinkAnalysis {
+                        total_links,
+                        unique_urls,
+                        broken_format_count,
+                    } => {
+                        urls = unique_urls;
+                        links = if unique_urls.len() >= MAX_UNIQUE_URLS {
+                            format!("{} (⚠ {}+ unique)", total_links, MAX_UNIQUE_URLS)
+                        } else {
+                            format!("{} ({} unique)", total_links, unique_urls.len())
+                        };
+                        if *broken_format_count > 0 {
+                            links.push_str(&format!(", {} malformed", broken_format_count));
+                        }
+                    }
+                    AnalysisResult::InkCoverage {
+                        avg_coverage,
+                        avg_cmyk,
+                        per_page,
+                        ..
+                    } => {
+                        let max_page = per_page.iter().cloned().fold(0.0, f64::max);
+                        let [c, m, y, k] = avg_cmyk.map(|channel| channel * 100.0);
+                        ink_coverage = format!(
+                            "{:.0}% (max {:.0}%, C{:.0} M{:.0} Y{:.0} K{:.0})",
+                            avg_coverage * 100.0,
+                            max_page * 100.0,
+                            c,
+                            m,
+                            y,
+                            k
+                        );
+                    }
+                    AnalysisResult::LayerAnalysis {
+                        has_layers: true,
+                        layer_names,
+                        visible_count,
+                        hidden_count,
+                        ..
+                    } => {
+                        names = layer_names;
+                        layers = if *hidden_count > 0 {
+                            format!(
+                                "{} ({} hidden)",
+                                visible_count + hidden_count,
+                                hidden_count
+                            )
+                        } else {
+                            visible_count.to_string()
+                        };
+                    }
+                    AnalysisResult::LayerAnalysis {
+                        unscanned_object_streams: true,
+                        ..
+                    } => {
+                        layers = "Unknown".to_string();
                     }
+                    AnalysisResult::ColorSpaceAnalysis {
+                        has_cmyk,
+                        has_rgb,
+                        has_spot_colors,
+                        spot_color_names,
+                        rgb_pages,
+                        cmyk_pages,
+                        gray_pages,
+                        ..
+                    } => {
+                        spot_colors = spot_color_names;
+                        let mut spaces = Vec::new();
+                        if *has_cmyk {
+                            spaces.push("CMYK".to_string());
+                        }
+                        if *has_rgb {
+                            spaces.push("RGB".to_string());
+                        }
+                        if *has_spot_colors {
+                            spaces.push(format!("{} spot", spot_color_names.len()));
+                        }
+                        if !spaces.is_empty() {
+                            color_space = spaces.join(" + ");
+                        }
+                        let image_pages: Vec<String> = [
+                            (*rgb_pages, "RGB"),
+                            (*cmyk_pages, "CMYK"),
+                            (*gray_pages, "gray"),
+                        ]
+                        .iter()
+                        .filter(|(count, _)| *count > 0)
+                        .map(|(count, space)| format!("{} {}", count, space))
+                        .collect();
+                        if !image_pages.is_empty() {
+                            color_space
+                                .push_str(&format!(" (image pages: {})", image_pages.join(", ")));
+                        }
+                    }
+                    AnalysisResult::TransparencyAnalysis {
+                        has_transparency,
+                        transparent_page_count,
+                        has_overprint,
+                        unscanned_object_streams,
+                    } => {
+                        transparency = if *has_transparency {
+                            format!("Yes ({} pages)", transparent_page_count)
+                        } else {
+                            "No".to_string()
+                        };
+                        overprint = match (*has_overprint, *unscanned_object_streams) {
+                            (true, _) => "Yes",
+                            (false, true) => "Unknown",
+                            (false, false) => "No",
+                        }
+                        .to_string();
+                    }
+                    AnalysisResult::PageRotationAnalysis {
+                        rotated_page_count,
+                        rotations,
+                    } => {
+                        rotated_pages = rotated_page_count.to_string();
+                        if !rotations.is_empty() {
+                            let pages: Vec<String> = rotations
+                                .iter()
+                                .map(|(page, degrees)| format!("{}: {}°", page, degrees))
+                                .collect();
+                            rotated_pages.push_str(&format!(" (p. {})", pages.join(", ")));
+                        }
+                    }
+                    AnalysisResult::Orientation {
+                        portrait,
+                        landscape,
+                    } => {
+                        orientation = format!("{} portrait, {} landscape", portrait, landscape);
+                        if *portrait > 0 && *landscape > 0 {
+                            orientation.push_str(" ⚠");
+                        }
+                    }
+                    AnalysisResult::FileSize {
+                        bytes,
+                        bytes_per_page,
+                    } => {
+                        total_bytes += bytes;
+                        file_size = format!(
+                            "{} ({}/page)",
+                            format_size(*bytes),
+                            format_size(*bytes_per_page)
+                        );
+                    }
+                    AnalysisResult::Annotations { total, by_type, .. } => {
+                        annotations = total.to_string();
+                        if !by_type.is_empty() {
+                            let types: Vec<String> = by_type
+                                .iter()
+                                .map(|(name, count)| format!("{} {}", count, name))
+                                .collect();
+                            annotations.push_str(&format!(" ({})", types.join(", ")));
+                        }
+                    }
+                    AnalysisResult::Outline {
+                        total,
+                        top_level,
+                        max_depth,
+                    } => outline = Some((*total, *top_level, *max_depth)),
+                    _ => {}
                 }
             }
 
+            // Once the page count is known, whichever result came first
+            let bookmarks = match outline {
+                Some((0, _, _))
+                    if self.warn_no_outline_pages > 0
+                        && pages as i64 > self.warn_no_outline_pages =>
+                {
+                    "⚠ None".to_string()
+                }
+                Some((0, _, _)) => "None".to_string(),
+                Some((total, top_level, max_depth)) => {
+                    format!("{} ({} top-level, {} levels)", total, top_level, max_depth)
+                }
+                None => "—".to_string(),
+            };
+
             total_pages += pages;
             total_bw += bw;
             total_color += color;
+            total_low_dpi += low_dpi;
 
             if self.show_per_pdf {
                 per_pdf.push(OutputRow {
                     filename: result.filename.clone(),
+                    path: Some(result.path.clone()),
                     values: vec![
                         ("Pages".to_string(), pages.to_string()),
                         ("B&W".to_string(), bw.to_string()),
                         ("Color".to_string(), color.to_string()),
+                        ("Low-DPI images".to_string(), low_dpi.to_string()),
+                        ("Page Sizes".to_string(), page_sizes),
+                        ("Security".to_string(), security),
+                        ("Form Fields".to_string(), form_fields),
+                        ("Attachments".to_string(), attachments),
+                        ("PDF Version".to_string(), pdf_version),
+                        ("Blank Pages".to_string(), blank_pages),
+                        ("Has Text".to_string(), has_text),
+                        ("Links".to_string(), links),
+                        ("Ink Coverage".to_string(), ink_coverage),
+                        ("Layers".to_string(), layers),
+                        ("Color Space".to_string(), color_space),
+                        ("Transparency".to_string(), transparency),
+                        ("Overprint".to_string(), overprint),
+                        ("Rotated Pages".to_string(), rotated_pages),
+                        ("Orientation".to_string(), orientation),
+                        ("File Size".to_string(), file_size),
+                        ("Annotations".to_string(), annotations),
+                        ("Bookmarks".to_string(), bookmarks),
                     ],
+                    highlight: false,
                 });
+                per_pdf_color_pages.push((color_numbers, color_coverage));
+                per_pdf_urls.push(urls);
+                per_pdf_layers.push(names);
+                per_pdf_spot_colors.push(spot_colors);
             }
         }
 
@@ -80,6 +493,12 @@ impl OutputModule for SummaryOutput {
             ("Total Pages".to_string(), total_pages.to_string()),
             ("Total B&W".to_string(), total_bw.to_string()),
             ("Total Color".to_string(), total_color.to_string()),
+            (
+                "Total Low-DPI images".to_string(),
+                total_low_dpi.to_string(),
+            ),
+            ("Total Words".to_string(), total_words.to_string()),
+            ("Total Size".to_string(), format_size(total_bytes)),
         ];
 
         let mut copyable_text = String::new();
@@ -87,27 +506,118 @@ impl OutputModule for SummaryOutput {
 
         if self.show_per_pdf {
             copyable_text.push_str("Per-PDF Breakdown:\n");
-            for row in &per_pdf {
+            for ((((row, (color_numbers, coverage)), urls), layer_names), spot_colors) in per_pdf
+                .iter()
+                .zip(&per_pdf_color_pages)
+                .zip(&per_pdf_urls)
+                .zip(&per_pdf_layers)
+                .zip(&per_pdf_spot_colors)
+            {
                 copyable_text.push_str(&format!(
-                    "  {}: {} pages ({} B&W, {} color)\n",
+                    "  {}: {} pages ({} B&W, {} color), {} low-DPI images, sizes: {}, security: {}, form fields: {}, attachments: {}, PDF {}, blank pages: {}, text: {}, links: {}, ink coverage: {}, layers: {}, color space: {}, transparency: {}, overprint: {}, rotated pages: {}, orientation: {}, file size: {}, annotations: {}, bookmarks: {}\n",
                     row.filename,
                     row.values[0].1,
                     row.values[1].1,
-                    row.values[2].1
+                    row.values[2].1,
+                    row.values[3].1,
+                    row.values[4].1,
+                    row.values[5].1,
+                    row.values[6].1,
+                    row.values[7].1,
+                    row.values[8].1,
+                    row.values[9].1,
+                    row.values[10].1,
+                    row.values[11].1,
+                    row.values[12].1,
+                    row.values[13].1,
+                    row.values[14].1,
+                    row.values[15].1,
+                    row.values[16].1,
+                    row.values[17].1,
+                    row.values[18].1,
+                    row.values[19].1,
+                    row.values[20].1,
+                    row.values[21].1
                 ));
+                if !color_numbers.is_empty() {
+                    let numbers: Vec<String> =
+                        color_numbers.iter().map(|n| n.to_string()).collect();
+                    copyable_text.push_str(&format!(
+                        "    Color pages: {} (color coverage {:.1}% average, {:.1}% max)\n",
+                        numbers.join(", "),
+                        coverage.0 * 100.0,
+                        coverage.1 * 100.0
+                    ));
+                }
+                if !layer_names.is_empty() {
+                    copyable_text.push_str(&format!("    Layers: {}\n", layer_names.join(", ")));
+                }
+                if !spot_colors.is_empty() {
+                    copyable_text.push_str(&format!("    Spot colors: {}\n", spot_colors.join(", ")));
+                }
+                if !urls.is_empty() {
+                    copyable_text.push_str(&format!("    URLs: {}\n", urls.join(", ")));
+                    if urls.len() >= MAX_UNIQUE_URLS {
+                        copyable_text.push_str(&format!(
+                            "    ⚠ Only the first {} unique URLs are listed\n",
+                            MAX_UNIQUE_URLS
+                        ));
+                    }
+                }
             }
             copyable_text.push('\n');
         }
 
         copyable_text.push_str(&format!("Total: {} pages ({} B&W, {} color)\n",
             total_pages, total_bw, total_color));
+        copyable_text.push_str(&format!(
+            "Images: {} total, {} low-DPI",
+            total_images, total_low_dpi
+        ));
+        if let Some(dpi) = lowest_dpi {
+            copyable_text.push_str(&format!(", lowest {:.0} DPI", dpi));
+        }
+        if !compressions.is_empty() {
+            copyable_text.push_str(&format!(" ({})", compressions.join(", ")));
+        }
+        copyable_text.push('\n');
+        copyable_text.push_str(&format!(
+            "Text: {} words, {} characters\n",
+            total_words, total_chars
+        ));
+        copyable_text.push_str(&format!("Size: {} total\n", format_size(total_bytes)));
 
         OutputData {
             title: "Page Summary".to_string(),
-            columns: vec!["File".to_string(), "Pages".to_string(), "B&W".to_string(), "Color".to_string()],
+            columns: vec![
+                "File".to_string(),
+                "Pages".to_string(),
+                "B&W".to_string(),
+                "Color".to_string(),
+                "Low-DPI images".to_string(),
+                "Page Sizes".to_string(),
+                "Security".to_string(),
+                "Form Fields".to_string(),
+                "Attachments".to_string(),
+                "PDF Version".to_string(),
+                "Blank Pages".to_string(),
+                "Has Text".to_string(),
+                "Links".to_string(),
+                "Ink Coverage".to_string(),
+                "Layers".to_string(),
+                "Color Space".to_string(),
+                "Transparency".to_string(),
+                "Overprint".to_string(),
+                "Rotated Pages".to_string(),
+                "Orientation".to_string(),
+                "File Size".to_string(),
+                "Annotations".to_string(),
+                "Bookmarks".to_string(),
+            ],
             per_pdf,
             totals,
             copyable_text,
+            file_extension: "txt",
         }
     }
 }
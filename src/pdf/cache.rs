@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use super::service::SinglePdfAnalysis;
+use crate::analyzer::PageRange;
+use crate::config::{AppConfig, Config};
+use crate::error::{AppError, Result};
+
+/// Key of a cached analysis. Covers everything the results depend on: the
+/// file's bytes, the settings, whether a password was given, the page range
+/// and the app version, so a changed file or setting is never served stale
+/// results.
+pub fn cache_key(
+    content_hash: &str,
+    config: &Config,
+    has_password: bool,
+    page_range: Option<PageRange>,
+) -> String {
+    // Through serde_json's sorted maps, so the same settings always hash the same
+    let settings = serde_json::to_value(config)
+        .map(|value| value.to_string())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update(content_hash);
+    hasher.update(settings);
+    hasher.update([has_password as u8]);
+    hasher.update(format!("{:?}", page_range));
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn entry_path(key: &str) -> Option<PathBuf> {
+    AppConfig::cache_dir().map(|dir| dir.join(format!("{}.json", key)))
+}
+
+/// Cached results for `key`, `None` on a miss or an unreadable entry.
+pub fn load(key: &str) -> Option<SinglePdfAnalysis> {
+    let content = fs::read_to_string(entry_path(key)?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn store(key: &str, analysis: &SinglePdfAnalysis) -> Result<()> {
+    let path = entry_path(key)
+        .ok_or_else(|| AppError::ConfigError("Could not determine config directory".to_string()))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content =
+        serde_json::to_string(analysis).map_err(|e| AppError::ConfigError(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
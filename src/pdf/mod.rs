@@ -1,50 +1,184 @@
+pub mod cache;
 pub mod service;
-// pub mod worker;
+pub mod worker;
 
+use std::fs::File;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use image::RgbaImage;
 use pdfium_render::prelude::*;
+use sha2::{Digest, Sha256};
 
 use crate::error::{AppError, Result};
 
 // pub use worker::{PdfRequest, PdfWorker};
 
+/// Map a pdfium load failure to an `AppError`, keeping encrypted documents
+/// distinguishable from broken ones.
+pub fn load_error(path: &Path, error: PdfiumError) -> AppError {
+    match error {
+        PdfiumError::PdfiumLibraryInternalError(PdfiumInternalError::PasswordError) => {
+            AppError::PasswordRequired {
+                path: path.display().to_string(),
+            }
+        }
+        e => AppError::PdfLoad {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// Hex encoded SHA-256 of a file's bytes, used to spot the same PDF added twice.
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Bounds of a rendered page in pixels, e.g. the first-page thumbnail
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThumbnailSize {
+    pub width: usize,
+    pub max_height: usize,
+}
+
+impl Default for ThumbnailSize {
+    fn default() -> Self {
+        Self {
+            width: 150,
+            max_height: 200,
+        }
+    }
+}
+
+/// What the file detail view lists for a single page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageDetails {
+    pub width_mm: f32,
+    pub height_mm: f32,
+    /// Clockwise degrees from the page's /Rotate
+    pub rotation: i32,
+    pub words: usize,
+}
+
 pub struct PdfFile {
     pub path: PathBuf,
     pub filename: String,
     pub page_count: usize,
     pub thumbnail: Option<RgbaImage>,
+    /// SHA-256 of the file content, `None` if the file couldn't be read
+    pub content_hash: Option<String>,
+    pub file_size_bytes: u64,
+    pub modified_at: Option<SystemTime>,
 }
 
 impl PdfFile {
-    pub fn load(path: PathBuf, pdfium: &Pdfium) -> Result<Self> {
+    /// Open a PDF and read what the file list shows, without rendering. The
+    /// thumbnail is left out; it is rendered separately with `render_thumbnail`.
+    pub fn load_metadata(path: PathBuf, password: Option<&str>, pdfium: &Pdfium) -> Result<Self> {
         let filename = path
             .file_name()
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
         let document = pdfium
-            .load_pdf_from_file(&path, None)
-            .map_err(|e| AppError::PdfLoad {
-                path: path.display().to_string(),
-                reason: e.to_string(),
-            })?;
+            .load_pdf_from_file(&path, password)
+            .map_err(|e| load_error(&path, e))?;
 
         let page_count = document.pages().len() as usize;
-
-        // Generate thumbnail from first page
-        let thumbnail = Self::generate_thumbnail(&document, 0).ok();
+        let content_hash = hash_file(&path).ok();
+        let metadata = std::fs::metadata(&path).ok();
 
         Ok(Self {
             path,
             filename,
             page_count,
-            thumbnail,
+            thumbnail: None,
+            content_hash,
+            file_size_bytes: metadata.as_ref().map_or(0, |m| m.len()),
+            modified_at: metadata.and_then(|m| m.modified().ok()),
         })
     }
 
-    fn generate_thumbnail(document: &PdfDocument, page_index: usize) -> Result<RgbaImage> {
+    /// Placeholder for a file pdfium cannot open without a password.
+    pub fn encrypted(path: PathBuf) -> Self {
+        let filename = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let content_hash = hash_file(&path).ok();
+        let metadata = std::fs::metadata(&path).ok();
+
+        Self {
+            path,
+            filename,
+            page_count: 0,
+            thumbnail: None,
+            content_hash,
+            file_size_bytes: metadata.as_ref().map_or(0, |m| m.len()),
+            modified_at: metadata.and_then(|m| m.modified().ok()),
+        }
+    }
+
+    /// Size, rotation and word count of every page, read without rendering.
+    pub fn page_details(
+        path: &Path,
+        password: Option<&str>,
+        pdfium: &Pdfium,
+    ) -> Result<Vec<PageDetails>> {
+        let document = pdfium
+            .load_pdf_from_file(path, password)
+            .map_err(|e| load_error(path, e))?;
+
+        let details = document
+            .pages()
+            .iter()
+            .map(|page| {
+                let rotation = match page.rotation() {
+                    Ok(PdfPageRenderRotation::Degrees90) => 90,
+                    Ok(PdfPageRenderRotation::Degrees180) => 180,
+                    Ok(PdfPageRenderRotation::Degrees270) => 270,
+                    _ => 0,
+                };
+                let text = page.text().map(|text| text.all()).unwrap_or_default();
+                PageDetails {
+                    width_mm: page.width().to_mm(),
+                    height_mm: page.height().to_mm(),
+                    rotation,
+                    words: text.split_whitespace().count(),
+                }
+            })
+            .collect();
+        Ok(details)
+    }
+
+    /// Render one page of a PDF, e.g. the first page as its thumbnail.
+    pub fn render_page(
+        path: &Path,
+        password: Option<&str>,
+        page_index: usize,
+        size: ThumbnailSize,
+        pdfium: &Pdfium,
+    ) -> Result<RgbaImage> {
+        let document = pdfium
+            .load_pdf_from_file(path, password)
+            .map_err(|e| load_error(path, e))?;
+        Self::render_page_image(&document, page_index, size)
+    }
+
+    fn render_page_image(
+        document: &PdfDocument,
+        page_index: usize,
+        thumbnail_size: ThumbnailSize,
+    ) -> Result<RgbaImage> {
         let page = document
             .pages()
             .get(page_index as u16)
@@ -54,8 +188,8 @@ impl PdfFile {
             })?;
 
         let render_config = PdfRenderConfig::new()
-            .set_target_width(150)
-            .set_maximum_height(200);
+            .set_target_width(thumbnail_size.width as i32)
+            .set_maximum_height(thumbnail_size.max_height as i32);
 
         let bitmap =
             page.render_with_config(&render_config)
@@ -1,66 +1,80 @@
 use crossbeam_channel as chan;
-use once_cell::sync::Lazy;
+use image::RgbaImage;
 use pdfium_render::prelude::*;
-use std::{
-    cell::OnceCell,
-    path::{Path, PathBuf},
-    sync::OnceLock,
-    thread,
-};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::OnceLock, thread, time::Instant};
 
 use crate::{
-    analyzer::{AnalysisResult, AnalyzerRegistry},
-    app::App,
+    analyzer::{
+        security_analysis::SecurityAnalyzer, AnalysisResult, AnalyzerRegistry, CancellationToken,
+        PageRange, PdfAnalysisResult,
+    },
+    config::Config,
     error::AppError,
-    pdf::PdfFile,
+    pdf::{cache, hash_file, load_error, PageDetails, PdfFile, ThumbnailSize},
 };
 
 /// A job to be executed on the Pdfium worker thread.
 /// It receives a mutable reference to Pdfium.
 type Job = Box<dyn FnOnce(&mut Pdfium) + Send + 'static>;
 
-/// Requests that can be sent to the pdfium service thread
-pub enum PdfSerivceRequest {
-    /// A job to be executed on the Pdfium worker thread
-    Job(Job),
+/// One PDF of a batch, queued with `PdfiumService::queue_analysis` or handed
+/// to an analysis worker process
+#[derive(Serialize, Deserialize)]
+pub struct AnalysisJob {
+    pub path: PathBuf,
+    pub password: Option<String>,
+    /// Pages to analyze, the whole document if `None`
+    pub page_range: Option<PageRange>,
+    pub config: Config,
+    /// Reuse results cached for the same file content and settings
+    pub use_cache: bool,
+    /// Worker processes get a token of their own, cancelled on request
+    #[serde(skip)]
+    pub cancel: CancellationToken,
+}
 
-    /// Shutdown the worker thread
-    Shutdown,
+/// What a queued analysis reports while it runs, by file path
+pub enum BatchEvent {
+    /// An analyzer started (`page_total` 0) or finished `page_done` pages
+    Progress {
+        path: PathBuf,
+        analyzer: String,
+        page_done: usize,
+        page_total: usize,
+    },
+    /// The file is done; `AppError::Cancelled` if the batch was cancelled first
+    Done {
+        path: PathBuf,
+        result: crate::error::Result<SinglePdfAnalysis>,
+    },
 }
 
 /// A handle you can clone and use from any thread.
+/// Jobs are run one after another on the Pdfium worker thread.
 #[derive(Clone, Debug)]
 pub struct PdfiumService {
-    tx: chan::Sender<PdfSerivceRequest>,
-    // worker: &'static OnceLock<PdfiumWorker>,
+    tx: chan::Sender<Job>,
 }
 
+/// The Pdfium worker thread, running for the lifetime of the application.
 #[derive(Debug)]
 pub struct PdfiumWorker {
-    handle: thread::JoinHandle<()>,
     service: PdfiumService,
 }
 
-// /// A handle you can clone and use from any thread.
-// #[derive(Clone)]
-// pub struct PdfiumService {
-//     tx: chan::Sender<Job>,
-// }
-
-// /// The message sent to the worker thread.
-// /// It contains a boxed function that will be executed on the worker thread,
-// /// receiving `&mut Pdfium`, and returning a boxed "any" result.
-// ///
-// /// Why `Box<dyn Any + Send>`?
-// /// So different calls can return different types, while remaining type-safe
-// /// at the call site via downcasting.
-
 /// Global singleton service handle (optional).
 static PDFIUM_WORKER: OnceLock<PdfiumWorker> = OnceLock::new();
 
 impl PdfiumWorker {
+    /// Spawn the worker thread owning the Pdfium instance. Pdfium isn't
+    /// thread-safe: pdfium-render locks the library for as long as an instance
+    /// lives, so a single thread runs every job.
+    /// Fails with `AppError::PdfLibrary` when the Pdfium library can't be loaded.
     pub fn spawn() -> crate::error::Result<()> {
-        let (tx, rx) = chan::unbounded::<PdfSerivceRequest>();
+        let (tx, rx) = chan::unbounded::<Job>();
+        // The worker reports whether it could load Pdfium before taking jobs
+        let (ready_tx, ready_rx) = chan::bounded::<crate::error::Result<()>>(1);
 
         // Spawn the dedicated worker thread.
         thread::Builder::new()
@@ -72,35 +86,38 @@ impl PdfiumWorker {
                         .or_else(|_| Pdfium::bind_to_system_library())
                         .map_err(|e| AppError::PdfLibrary {
                             reason: e.to_string(),
-                        })
-                        .expect("Failed to load Pdfium library");
+                        });
+                let pdfium_binding = match pdfium_binding {
+                    Ok(binding) => binding,
+                    Err(err) => {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                };
 
                 let mut pdfium = Pdfium::new(pdfium_binding);
+                let _ = ready_tx.send(Ok(()));
 
                 // Process jobs forever.
                 for job in rx.iter() {
-                    match job {
-                        PdfSerivceRequest::Job(j) => j(&mut pdfium),
-                        PdfSerivceRequest::Shutdown => break,
-                    }
+                    job(&mut pdfium);
                 }
-            })
-            .map(|handle| {
-                // Store the service handle globally.
-                let worker = PdfiumWorker {
-                    handle,
-                    service: PdfiumService {
-                        tx,
-                        // worker: &PDFIUM_WORKER,
-                    },
-                };
+            })?;
 
-                PDFIUM_WORKER
-                    .set(worker)
-                    .expect("PdfiumWorker already initialized");
+        ready_rx.recv().map_err(|_| AppError::PdfLibrary {
+            reason: "Pdfium worker stopped during startup".to_string(),
+        })??;
 
-                Ok(())
-            })?
+        // Store the service handle globally.
+        let worker = PdfiumWorker {
+            service: PdfiumService { tx },
+        };
+
+        PDFIUM_WORKER
+            .set(worker)
+            .expect("PdfiumWorker already initialized");
+
+        Ok(())
     }
 
     /// Get the global PdfiumService handle.
@@ -116,53 +133,267 @@ impl PdfiumWorker {
 }
 
 /// Result of analyzing a single PDF
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SinglePdfAnalysis {
     pub filename: String,
     pub path: String,
     pub results: Vec<AnalysisResult>,
     pub errors: Vec<String>,
+    /// Kept from the original run on cache hits
+    #[serde(default)]
+    pub analyzer_timings: Vec<(String, u64)>,
+}
+
+impl From<SinglePdfAnalysis> for PdfAnalysisResult {
+    fn from(analysis: SinglePdfAnalysis) -> Self {
+        Self {
+            filename: analysis.filename,
+            path: analysis.path,
+            results: analysis.results,
+            errors: analysis.errors,
+            analyzer_timings: analysis.analyzer_timings,
+            page_range: None,
+        }
+    }
 }
 
 impl PdfiumService {
-    pub fn sender(&self) -> chan::Sender<PdfSerivceRequest> {
-        self.tx.clone()
+    pub fn load_pdf(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+    ) -> crate::error::Result<PdfFile> {
+        self.call(move |pdfium| PdfFile::load_metadata(path, password.as_deref(), pdfium))
+    }
+
+    /// Queue opening a PDF for the file list on the Pdfium worker and
+    /// return immediately. The file is sent to `done` with its path.
+    pub fn queue_load(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+        done: chan::Sender<(PathBuf, crate::error::Result<PdfFile>)>,
+    ) {
+        self.cast(move |pdfium| {
+            let file = PdfFile::load_metadata(path.clone(), password.as_deref(), pdfium);
+            let _ = done.send((path, file));
+        })
     }
 
-    pub fn load_pdf(&self, path: PathBuf) -> crate::error::Result<PdfFile> {
-        self.call(|pdfium| PdfFile::load(path, &pdfium))
+    /// Queue opening an encrypted PDF with `password` on the Pdfium worker
+    /// and return immediately. The file is sent to `done` with its path and
+    /// the password tried.
+    pub fn queue_unlock(
+        &self,
+        path: PathBuf,
+        password: String,
+        done: chan::Sender<(PathBuf, String, crate::error::Result<PdfFile>)>,
+    ) {
+        self.cast(move |pdfium| {
+            let file = PdfFile::load_metadata(path.clone(), Some(&password), pdfium);
+            let _ = done.send((path, password, file));
+        })
+    }
+
+    /// Queue rendering the first page of a PDF on the Pdfium worker and
+    /// return immediately. The thumbnail is sent to `done` with the path.
+    pub fn queue_thumbnail(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+        thumbnail_size: ThumbnailSize,
+        done: chan::Sender<(PathBuf, crate::error::Result<RgbaImage>)>,
+    ) {
+        self.cast(move |pdfium| {
+            let thumbnail =
+                PdfFile::render_page(&path, password.as_deref(), 0, thumbnail_size, pdfium);
+            let _ = done.send((path, thumbnail));
+        })
     }
 
-    pub fn analyze_pdf(&self, path: PathBuf) -> crate::error::Result<SinglePdfAnalysis> {
-        self.call(|pdfium| {
-            let registry = AnalyzerRegistry::default();
-            Self::analyze_pdf_by_registry(pdfium, &registry, path)
+    /// Queue a check that a PDF still opens, without rendering or analyzing
+    /// anything. The outcome is sent to `done` with the path.
+    pub fn queue_validation(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+        done: chan::Sender<(PathBuf, crate::error::Result<()>)>,
+    ) {
+        self.cast(move |pdfium| {
+            let result = pdfium
+                .load_pdf_from_file(&path, password.as_deref())
+                .map(|_document| ())
+                .map_err(|e| load_error(&path, e));
+            let _ = done.send((path, result));
         })
     }
 
+    /// Queue reading the per-page details of a PDF for the file detail view.
+    pub fn queue_page_details(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+        done: chan::Sender<crate::error::Result<Vec<PageDetails>>>,
+    ) {
+        self.cast(move |pdfium| {
+            let _ = done.send(PdfFile::page_details(&path, password.as_deref(), pdfium));
+        })
+    }
+
+    /// Queue rendering one page of a PDF for the preview window. The image is
+    /// sent to `done` with the 0-based page index.
+    pub fn queue_page_render(
+        &self,
+        path: PathBuf,
+        password: Option<String>,
+        page_index: usize,
+        size: ThumbnailSize,
+        done: chan::Sender<(usize, crate::error::Result<RgbaImage>)>,
+    ) {
+        self.cast(move |pdfium| {
+            let page = PdfFile::render_page(&path, password.as_deref(), page_index, size, pdfium);
+            let _ = done.send((page_index, page));
+        })
+    }
+
+    /// Queue the analysis of one PDF on the Pdfium worker and return immediately.
+    /// Progress and the result are sent to `events`, so a whole batch can be
+    /// dispatched up front and collected as the files finish.
+    pub fn queue_analysis(&self, job: AnalysisJob, events: chan::Sender<BatchEvent>) {
+        self.cast(move |pdfium| {
+            let path = job.path.clone();
+            // Files still queued when the batch is cancelled are skipped entirely
+            let result = if job.cancel.is_cancelled() {
+                Err(AppError::Cancelled)
+            } else {
+                let progress = |analyzer: &str, page_done, page_total| {
+                    let _ = events.send(BatchEvent::Progress {
+                        path: path.clone(),
+                        analyzer: analyzer.to_string(),
+                        page_done,
+                        page_total,
+                    });
+                };
+                Self::analyze_pdf_cached(pdfium, job, &progress)
+            };
+            let _ = events.send(BatchEvent::Done { path, result });
+        })
+    }
+
+    /// Analyze a queued PDF, answering from the result cache when the file
+    /// content and settings are unchanged since an earlier analysis.
+    fn analyze_pdf_cached(
+        pdfium: &Pdfium,
+        job: AnalysisJob,
+        progress: &dyn Fn(&str, usize, usize),
+    ) -> crate::error::Result<SinglePdfAnalysis> {
+        let key = job
+            .use_cache
+            .then(|| hash_file(&job.path).ok())
+            .flatten()
+            .map(|hash| {
+                cache::cache_key(&hash, &job.config, job.password.is_some(), job.page_range)
+            });
+
+        if let Some(mut analysis) = key.as_deref().and_then(cache::load) {
+            // The same content may have been analyzed under another name
+            analysis.filename = job
+                .path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            analysis.path = job.path.display().to_string();
+            return Ok(analysis);
+        }
+
+        let mut registry = AnalyzerRegistry::default();
+        registry.apply_config(&job.config);
+        let analysis = Self::analyze_pdf_by_registry(
+            pdfium,
+            &registry,
+            job.path,
+            job.password.as_deref(),
+            job.page_range,
+            &job.cancel,
+            progress,
+        )?;
+
+        // A failed write only costs the next run its speed-up
+        if let Some(ref key) = key {
+            let _ = cache::store(key, &analysis);
+        }
+        Ok(analysis)
+    }
+
     fn analyze_pdf_by_registry(
         pdfium: &Pdfium,
         registry: &AnalyzerRegistry,
         path: PathBuf,
+        password: Option<&str>,
+        page_range: Option<PageRange>,
+        cancel: &CancellationToken,
+        progress: &dyn Fn(&str, usize, usize),
     ) -> crate::error::Result<SinglePdfAnalysis> {
         let filename = path
             .file_name()
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
-        let document = pdfium
-            .load_pdf_from_file(&path, None)
-            .map_err(|e| AppError::PdfLoad {
-                path: path.display().to_string(),
-                reason: e.to_string(),
-            })?;
+        // Report encrypted documents instead of failing, so they still show up in the results
+        let document = match pdfium.load_pdf_from_file(&path, password) {
+            Ok(document) => document,
+            Err(e) => match load_error(&path, e) {
+                err @ AppError::PasswordRequired { .. } => {
+                    return Ok(SinglePdfAnalysis {
+                        filename,
+                        path: path.display().to_string(),
+                        results: vec![SecurityAnalyzer::password_required()],
+                        errors: vec![err.to_string()],
+                        analyzer_timings: vec![],
+                    });
+                }
+                err => return Err(err),
+            },
+        };
+
+        let page_count = document.pages().len() as usize;
+        let pages = page_range.map_or(0..page_count, |range| range.indices(page_count));
 
         let mut results = Vec::new();
         let mut errors = Vec::new();
+        let mut analyzer_timings = Vec::new();
 
-        for analyzer in registry.analyzers() {
-            match analyzer.analyze(&document, &path) {
-                Ok(result) => results.push(result),
+        for analyzer in registry.enabled_analyzers() {
+            // Stop between analyzers so a cancelled batch doesn't keep the worker busy
+            if cancel.is_cancelled() {
+                return Err(AppError::Cancelled);
+            }
+
+            progress(analyzer.name(), 0, 0);
+            let page_progress =
+                |page_done, page_total| progress(analyzer.name(), page_done, page_total);
+
+            let started = Instant::now();
+            let result = analyzer.analyze_with_progress(
+                &document,
+                &path,
+                pages.clone(),
+                &page_progress,
+                cancel,
+            );
+            analyzer_timings.push((
+                analyzer.name().to_string(),
+                started.elapsed().as_millis() as u64,
+            ));
+
+            match result {
+                Ok(result) => {
+                    for error in analyzer.result_errors(&result) {
+                        errors.push(format!("{}: {}", analyzer.name(), error));
+                    }
+                    results.push(result);
+                }
+                Err(AppError::Cancelled) => return Err(AppError::Cancelled),
                 Err(e) => errors.push(format!("{}: {}", analyzer.name(), e)),
             }
         }
@@ -172,6 +403,7 @@ impl PdfiumService {
             path: path.display().to_string(),
             results,
             errors,
+            analyzer_timings,
         })
     }
 
@@ -194,7 +426,7 @@ impl PdfiumService {
         });
 
         self.tx
-            .send(PdfSerivceRequest::Job(job))
+            .send(job)
             .expect("Pdfium worker thread seems to have stopped");
 
         // Wait for the response.
@@ -208,12 +440,7 @@ impl PdfiumService {
     {
         let job: Job = Box::new(move |pdfium: &mut Pdfium| f(pdfium));
         self.tx
-            .send(PdfSerivceRequest::Job(job))
+            .send(job)
             .expect("Pdfium worker thread seems to have stopped");
     }
-
-    /// Shutdown the worker thread
-    pub fn shutdown(&self) {
-        let _ = self.tx.send(PdfSerivceRequest::Shutdown);
-    }
 }
@@ -0,0 +1,295 @@
+use crossbeam_channel as chan;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    analyzer::CancellationToken,
+    error::{AppError, Result},
+    pdf::service::{AnalysisJob, BatchEvent, PdfiumWorker, SinglePdfAnalysis},
+};
+
+/// Hidden command line flag starting the app as an analysis worker process
+pub const WORKER_FLAG: &str = "--analysis-worker";
+
+/// How often a worker waiting for a reply checks whether the batch was cancelled
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+/// A line on the stdin of a worker process
+#[derive(Serialize, Deserialize)]
+enum WorkerRequest {
+    Analyze(Box<AnalysisJob>),
+    /// Stop the file being analyzed; the worker takes no more jobs after that
+    Cancel,
+}
+
+/// A line on the stdout of a worker process
+#[derive(Serialize, Deserialize)]
+enum WorkerReply {
+    /// Sent once at start, with the error if Pdfium couldn't be loaded
+    Ready(std::result::Result<(), String>),
+    Progress {
+        analyzer: String,
+        page_done: usize,
+        page_total: usize,
+    },
+    Done(std::result::Result<SinglePdfAnalysis, String>),
+    Cancelled,
+}
+
+/// Worker processes analyzing the files of a batch in parallel.
+///
+/// pdfium-render locks the library for as long as a Pdfium instance lives, so
+/// instances on several threads of one process would run one at a time.
+/// Each worker is this executable started with `--analysis-worker`, loading
+/// its own Pdfium and analyzing one file at a time.
+pub struct PdfWorkerPool {
+    workers: Vec<WorkerProcess>,
+}
+
+struct WorkerProcess {
+    child: Child,
+    stdin: ChildStdin,
+    replies: chan::Receiver<WorkerReply>,
+}
+
+impl PdfWorkerPool {
+    /// Start `count` worker processes and wait until each has loaded Pdfium.
+    pub fn spawn(count: usize) -> Result<Self> {
+        let exe = std::env::current_exe()?;
+        let mut workers = Vec::with_capacity(count);
+        for _ in 0..count {
+            workers.push(WorkerProcess::spawn(&exe)?);
+        }
+        Ok(Self { workers })
+    }
+
+    /// Hand out `jobs` to the workers as they become free and return
+    /// immediately. Progress and results are sent to `events`, which is
+    /// dropped once every worker has stopped.
+    pub fn queue_batch(
+        self,
+        jobs: Vec<AnalysisJob>,
+        cancel: CancellationToken,
+        events: chan::Sender<BatchEvent>,
+    ) {
+        let (job_tx, job_rx) = chan::unbounded();
+        for job in jobs {
+            let _ = job_tx.send(job);
+        }
+        drop(job_tx);
+
+        for mut worker in self.workers {
+            let jobs = job_rx.clone();
+            let cancel = cancel.clone();
+            let events = events.clone();
+            thread::spawn(move || {
+                while !cancel.is_cancelled() {
+                    let Ok(job) = jobs.recv() else {
+                        break;
+                    };
+                    let path = job.path.clone();
+                    // Files of a stopped worker are left to the others
+                    if let Err(err) = worker.analyze(job, &cancel, &events) {
+                        let _ = events.send(BatchEvent::Done {
+                            path,
+                            result: Err(err),
+                        });
+                        break;
+                    }
+                }
+                worker.stop();
+            });
+        }
+    }
+}
+
+impl WorkerProcess {
+    fn spawn(exe: &std::path::Path) -> Result<Self> {
+        let mut child = Command::new(exe)
+            .arg(WORKER_FLAG)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let (reply_tx, replies) = chan::unbounded();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(reply) = line
+                    .map_err(|_| ())
+                    .and_then(|line| serde_json::from_str::<WorkerReply>(&line).map_err(|_| ()))
+                else {
+                    break;
+                };
+                if reply_tx.send(reply).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let worker = Self {
+            child,
+            stdin,
+            replies,
+        };
+        match worker.replies.recv() {
+            Ok(WorkerReply::Ready(Ok(()))) => Ok(worker),
+            Ok(WorkerReply::Ready(Err(reason))) => {
+                worker.stop();
+                Err(AppError::PdfLibrary { reason })
+            }
+            _ => {
+                worker.stop();
+                Err(worker_stopped())
+            }
+        }
+    }
+
+    fn send(&mut self, request: &WorkerRequest) -> Result<()> {
+        let line =
+            serde_json::to_string(request).map_err(|e| AppError::AnalysisWorker(e.to_string()))?;
+        writeln!(self.stdin, "{}", line)?;
+        Ok(self.stdin.flush()?)
+    }
+
+    /// Analyze one file, forwarding its progress and result to `events`.
+    /// Fails if the worker process stopped, without sending the result.
+    fn analyze(
+        &mut self,
+        job: AnalysisJob,
+        cancel: &CancellationToken,
+        events: &chan::Sender<BatchEvent>,
+    ) -> Result<()> {
+        let path = job.path.clone();
+        self.send(&WorkerRequest::Analyze(Box::new(job)))?;
+
+        let mut cancel_sent = false;
+        loop {
+            if cancel.is_cancelled() && !cancel_sent {
+                self.send(&WorkerRequest::Cancel)?;
+                cancel_sent = true;
+            }
+            let reply = match self.replies.recv_timeout(CANCEL_POLL) {
+                Ok(reply) => reply,
+                Err(chan::RecvTimeoutError::Timeout) => continue,
+                Err(chan::RecvTimeoutError::Disconnected) => return Err(worker_stopped()),
+            };
+            let result = match reply {
+                WorkerReply::Progress {
+                    analyzer,
+                    page_done,
+                    page_total,
+                } => {
+                    let _ = events.send(BatchEvent::Progress {
+                        path: path.clone(),
+                        analyzer,
+                        page_done,
+                        page_total,
+                    });
+                    continue;
+                }
+                WorkerReply::Ready(_) => continue,
+                WorkerReply::Done(result) => result.map_err(AppError::AnalysisWorker),
+                WorkerReply::Cancelled => Err(AppError::Cancelled),
+            };
+            let _ = events.send(BatchEvent::Done { path, result });
+            return Ok(());
+        }
+    }
+
+    /// Closing stdin lets the worker finish its main loop and exit.
+    fn stop(self) {
+        let Self {
+            mut child, stdin, ..
+        } = self;
+        drop(stdin);
+        let _ = child.wait();
+    }
+}
+
+fn worker_stopped() -> AppError {
+    AppError::AnalysisWorker("Analysis worker process stopped unexpectedly".to_string())
+}
+
+/// Main loop of a process started with `--analysis-worker`: analyze the jobs
+/// read from stdin on the Pdfium worker and write progress and results to
+/// stdout, one JSON line each. Returns when stdin is closed.
+pub fn serve(pdfium_error: Option<AppError>) {
+    let mut stdout = io::stdout().lock();
+    let pdf_service = match pdfium_error.map_or_else(PdfiumWorker::service, Err) {
+        Ok(pdf_service) => pdf_service,
+        Err(err) => {
+            reply(&mut stdout, &WorkerReply::Ready(Err(err.to_string())));
+            return;
+        }
+    };
+    if !reply(&mut stdout, &WorkerReply::Ready(Ok(()))) {
+        return;
+    }
+
+    let cancel = CancellationToken::new();
+    let (job_tx, job_rx) = chan::unbounded();
+    {
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                match serde_json::from_str(&line) {
+                    Ok(WorkerRequest::Analyze(job)) => {
+                        let _ = job_tx.send(*job);
+                    }
+                    Ok(WorkerRequest::Cancel) => cancel.cancel(),
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+
+    for mut job in job_rx {
+        job.cancel = cancel.clone();
+        let (events_tx, events_rx) = chan::unbounded();
+        pdf_service.queue_analysis(job, events_tx);
+        for event in events_rx {
+            let message = match event {
+                BatchEvent::Progress {
+                    analyzer,
+                    page_done,
+                    page_total,
+                    ..
+                } => WorkerReply::Progress {
+                    analyzer,
+                    page_done,
+                    page_total,
+                },
+                BatchEvent::Done {
+                    result: Err(AppError::Cancelled),
+                    ..
+                } => WorkerReply::Cancelled,
+                BatchEvent::Done { result, .. } => {
+                    WorkerReply::Done(result.map_err(|e| e.to_string()))
+                }
+            };
+            if !reply(&mut stdout, &message) {
+                return;
+            }
+        }
+    }
+}
+
+/// Write one reply line; false once the app has gone away.
+fn reply(stdout: &mut impl Write, reply: &WorkerReply) -> bool {
+    serde_json::to_string(reply).is_ok_and(|line| {
+        writeln!(stdout, "{}", line)
+            .and_then(|_| stdout.flush())
+            .is_ok()
+    })
+}
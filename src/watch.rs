@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::analyzer::{AnalysisResult, CancellationToken, PdfAnalysisResult};
+use crate::app::{run_analysis, AnalysisMessage};
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export::csv;
+use crate::pdf::service::PdfiumService;
+
+/// Name of the CSV log written into the watched folder
+pub const LOG_FILE_NAME: &str = "pdf_analyzer_log.csv";
+
+/// A file is analyzed once it saw no events for this long and its size stopped
+/// changing, so files still being copied are not picked up half-written.
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches a folder and analyzes every PDF that appears in it on the Pdfium
+/// worker, appending one line per file to the folder's CSV log.
+/// Watching stops when this is dropped.
+pub struct FolderWatcher {
+    pub folder: PathBuf,
+    _watcher: RecommendedWatcher,
+    /// Problems while watching, analyzing or writing the log
+    pub errors: Receiver<String>,
+}
+
+/// A new PDF waiting for its writes to settle
+struct PendingFile {
+    last_event: Instant,
+    size: Option<u64>,
+}
+
+impl FolderWatcher {
+    pub fn start(
+        folder: PathBuf,
+        pdf_service: PdfiumService,
+        config: Config,
+        use_cache: bool,
+    ) -> Result<Self> {
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(event_tx).map_err(watch_error)?;
+        watcher
+            .watch(&folder, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        let (error_tx, error_rx) = mpsc::channel();
+        let log_path = folder.join(LOG_FILE_NAME);
+        thread::Builder::new()
+            .name("folder-watcher".to_string())
+            .spawn(move || {
+                watch_loop(event_rx, log_path, pdf_service, config, use_cache, error_tx)
+            })?;
+
+        Ok(Self {
+            folder,
+            _watcher: watcher,
+            errors: error_rx,
+        })
+    }
+}
+
+fn watch_error(err: notify::Error) -> AppError {
+    AppError::WatchError(err.to_string())
+}
+
+fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+}
+
+/// Collect new PDFs from the watcher events and analyze them once settled.
+/// Ends when the watcher is dropped and its event channel disconnects.
+fn watch_loop(
+    events: Receiver<notify::Result<Event>>,
+    log_path: PathBuf,
+    pdf_service: PdfiumService,
+    config: Config,
+    use_cache: bool,
+    errors: Sender<String>,
+) {
+    let mut pending: HashMap<PathBuf, PendingFile> = HashMap::new();
+
+    loop {
+        match events.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => {
+                let is_new = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+                );
+                for path in event.paths.into_iter().filter(|p| is_pdf(p)) {
+                    // Writes to a file we haven't seen created are edits, not new files
+                    if is_new || pending.contains_key(&path) {
+                        let file = pending.entry(path).or_insert(PendingFile {
+                            last_event: Instant::now(),
+                            size: None,
+                        });
+                        file.last_event = Instant::now();
+                    }
+                }
+            }
+            Ok(Err(err)) => {
+                let _ = errors.send(format!("Folder watch error: {}", err));
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let mut settled = Vec::new();
+        for (path, file) in pending.iter_mut() {
+            if file.last_event.elapsed() < SETTLE_TIME {
+                continue;
+            }
+            let size = fs::metadata(path).ok().map(|m| m.len());
+            if size.is_some() && size == file.size {
+                settled.push(path.clone());
+            } else {
+                file.size = size;
+                file.last_event = Instant::now();
+            }
+        }
+
+        for path in settled {
+            pending.remove(&path);
+            if let Err(e) = analyze_and_log(&path, &log_path, &pdf_service, &config, use_cache) {
+                let _ = errors.send(e);
+            }
+        }
+
+        // Files deleted or moved away before settling are dropped
+        pending.retain(|path, _| path.exists());
+    }
+}
+
+fn analyze_and_log(
+    path: &Path,
+    log_path: &Path,
+    pdf_service: &PdfiumService,
+    config: &Config,
+    use_cache: bool,
+) -> std::result::Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    run_analysis(
+        vec![(path.to_path_buf(), None, None)],
+        pdf_service.clone(),
+        1,
+        config.clone(),
+        use_cache,
+        CancellationToken::new(),
+        tx,
+    );
+
+    let mut failure = None;
+    let mut result = None;
+    for message in rx.try_iter() {
+        match message {
+            AnalysisMessage::Error(e) => failure = Some(e),
+            AnalysisMessage::Complete(mut results) | AnalysisMessage::Cancelled(mut results) => {
+                result = results.pop()
+            }
+            AnalysisMessage::Progress(_) => {}
+        }
+    }
+
+    let line = log_line(path, result.as_ref(), failure.as_deref());
+    append_log(log_path, &line)
+        .map_err(|e| format!("Failed to write {}: {}", log_path.display(), e))?;
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn log_line(path: &Path, result: Option<&PdfAnalysisResult>, failure: Option<&str>) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        .to_string();
+    let filename = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut pages = String::new();
+    let mut bw_pages = String::new();
+    let mut color_pages = String::new();
+    let mut errors = Vec::new();
+
+    if let Some(result) = result {
+        for r in &result.results {
+            match r {
+                AnalysisResult::PageCount { total } => pages = total.to_string(),
+                AnalysisResult::ColorAnalysis {
+                    bw_pages: bw,
+                    color_pages: color,
+                    ..
+                }
+                | AnalysisResult::ColorAnalysisDetailed {
+                    bw_pages: bw,
+                    color_pages: color,
+                    ..
+                } => {
+                    bw_pages = bw.to_string();
+                    color_pages = color.to_string();
+                }
+                _ => {}
+            }
+        }
+        errors.extend(result.errors.iter().cloned());
+    }
+    errors.extend(failure.map(str::to_string));
+
+    csv::record([
+        timestamp,
+        filename,
+        pages,
+        bw_pages,
+        color_pages,
+        errors.join("; "),
+    ])
+}
+
+fn append_log(log_path: &Path, line: &str) -> std::io::Result<()> {
+    let is_new = !log_path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+    if is_new {
+        writeln!(file, "timestamp,file,pages,bw_pages,color_pages,errors")?;
+    }
+    writeln!(file, "{}", line)
+}